@@ -8,7 +8,7 @@ use typst_library::diag::{
 use typst_library::engine::{Engine, Sink, Traced};
 use typst_library::foundations::{
     Arg, Args, Binding, Capturer, Closure, Content, Context, Func, NativeElement, Scope,
-    Scopes, SymbolElem, Value,
+    Scopes, SymbolElem, Type, Value,
 };
 use typst_library::introspection::Introspector;
 use typst_library::math::LrElem;
@@ -328,8 +328,23 @@ fn eval_field_call(
     };
 
     let field_span = field.span();
+    if !matches!(target, Value::Type(_))
+        && let Some(callee) =
+            target.ty().field_checked(&field, || (&mut vm.engine, field_span))
+    {
+        // This is the common case of a method call on a value.
+        args.insert(0, target_expr.span(), target);
+        return Ok(FieldCall::Normal(callee.clone(), args));
+    }
+
     let sink = (&mut vm.engine, field_span);
-    if let Some(callee) = target.ty().scope().get(&field) {
+    if let Value::Type(ty) = &target
+        && ty.scope().get(&field).is_none()
+        && let Some(callee) = Type::method(&field)
+    {
+        // Some functions of `type` are also methods of types, e.g.
+        // `str.fields()`. The others, like `type.all`, are only found in
+        // the scope of `type` itself below.
         args.insert(0, target_expr.span(), target);
         Ok(FieldCall::Normal(callee.read_checked(sink).clone(), args))
     } else if let Value::Content(content) = &target {
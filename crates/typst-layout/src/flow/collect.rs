@@ -1,25 +1,29 @@
 use std::cell::{LazyCell, RefCell};
 use std::fmt::{self, Debug, Formatter};
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 
 use bumpalo::Bump;
 use bumpalo::boxed::Box as BumpBox;
 use comemo::{Track, Tracked, TrackedMut};
+use ecow::eco_vec;
 use typst_library::World;
-use typst_library::diag::{SourceResult, bail, warning};
+use typst_library::diag::{SourceDiagnostic, SourceResult, bail, error, warning};
 use typst_library::engine::{Engine, Route, Sink, Traced};
-use typst_library::foundations::{Packed, Resolve, Smart, StyleChain};
+use typst_library::foundations::{Content, Packed, Resolve, Smart, StyleChain};
 use typst_library::introspection::{
     Introspector, Location, Locator, LocatorLink, SplitLocator, Tag, TagElem,
 };
 use typst_library::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockElem, ColbreakElem, FixedAlignment, FlushElem,
-    Fr, Fragment, Frame, PagebreakElem, PlaceElem, PlacementScope, Ratio, Region,
-    Regions, Rel, Size, Sizing, Spacing, VElem,
+    Abs, AlignElem, Alignment, Axes, BlockBreak, BlockElem, ColbreakElem, ColpenaltyElem,
+    ColumnsPin, FixedAlignment, FlushElem, Fr, Fragment, Frame, FrameItem, PagebreakElem,
+    PlaceElem, PlacementScope, Ratio, Region, Regions, Rel, Size, Sizing, Spacing,
+    Transform, VElem,
 };
 use typst_library::model::ParElem;
 use typst_library::routines::{Pair, Routines};
 use typst_library::text::TextElem;
+use typst_syntax::{Span, Spanned};
 use typst_utils::SliceExt;
 
 use super::{FlowMode, layout_multi_block, layout_single_block};
@@ -37,6 +41,8 @@ pub fn collect<'a>(
     locator: Locator<'a>,
     base: Size,
     expand: bool,
+    columns: NonZeroUsize,
+    spanning: bool,
     mode: FlowMode,
 ) -> SourceResult<Vec<Child<'a>>> {
     Collector {
@@ -46,12 +52,27 @@ pub fn collect<'a>(
         locator: locator.split(),
         base,
         expand,
+        columns,
+        spanning,
         output: Vec::with_capacity(children.len()),
         par_situation: ParSituation::First,
     }
     .run(mode)
 }
 
+/// The error for a column break to a column beyond the `count` available
+/// ones.
+pub fn missing_column(to: Spanned<NonZeroUsize>, count: usize) -> SourceDiagnostic {
+    error!(
+        to.span,
+        "cannot break to column {}", to.v;
+        hint: "there {} only {} column{}",
+        if count == 1 { "is" } else { "are" },
+        count,
+        if count == 1 { "" } else { "s" },
+    )
+}
+
 /// State for collection.
 struct Collector<'a, 'x, 'y> {
     engine: &'x mut Engine<'y>,
@@ -59,6 +80,11 @@ struct Collector<'a, 'x, 'y> {
     children: &'x [Pair<'a>],
     base: Size,
     expand: bool,
+    /// The largest number of columns in any region of the flow.
+    columns: NonZeroUsize,
+    /// Whether to prepare blocks for spanning all columns in case they are
+    /// too wide.
+    spanning: bool,
     locator: SplitLocator<'a>,
     output: Vec<Child<'a>>,
     par_situation: ParSituation,
@@ -89,7 +115,23 @@ impl<'a> Collector<'a, '_, '_> {
             } else if child.is::<FlushElem>() {
                 self.output.push(Child::Flush);
             } else if let Some(elem) = child.to_packed::<ColbreakElem>() {
-                self.output.push(Child::Break(elem.weak.get(styles)));
+                if elem.when.get(styles) {
+                    let to = elem.to.get(styles).map(|to| Spanned::new(to, elem.span()));
+                    // The number of columns depends on the enclosing container,
+                    // so this is the earliest point at which we can reject a
+                    // target that no region has. The distributor checks the
+                    // target against the columns of the actual region.
+                    if let Some(to) = to
+                        && to.v > self.columns
+                    {
+                        return Err(eco_vec![missing_column(to, self.columns.get())]);
+                    }
+                    self.output.push(Child::Break(elem.weak.get(styles), to));
+                }
+            } else if let Some(elem) = child.to_packed::<ColpenaltyElem>() {
+                self.output.push(Child::Penalty(elem.penalty));
+            } else if let Some(elem) = child.to_packed::<ColumnsPin>() {
+                self.pin(elem, styles)?;
             } else if child.is::<PagebreakElem>() {
                 bail!(
                     child.span(), "pagebreaks are not allowed inside of containers";
@@ -224,8 +266,19 @@ impl<'a> Collector<'a, '_, '_> {
                 frame.height()
             };
 
-            self.output
-                .push(Child::Line(self.boxed(LineChild { frame, align, need })));
+            // A break after this line is a sentence break if it doesn't
+            // leave a widow or orphan.
+            let lonely = (prevent_orphans && i == 0) || (prevent_widows && i + 2 == len);
+            let sentence = !lonely && ends_sentence(&frame);
+
+            self.output.push(Child::Line(self.boxed(LineChild {
+                frame,
+                align,
+                need,
+                sentence,
+                index: i,
+                lines: len,
+            })));
         }
     }
 
@@ -249,24 +302,57 @@ impl<'a> Collector<'a, '_, '_> {
             Smart::Custom(Spacing::Fr(fr)) => Child::Fr(fr),
         };
 
+        if elem.break_before.get(styles) == Some(BlockBreak::Column) {
+            // Break before the start tags in front of the block, so that they
+            // end up in the same column as the block.
+            let at = self
+                .output
+                .iter()
+                .rposition(|child| !matches!(child, Child::Tag(Tag::Start(..))))
+                .map_or(0, |i| i + 1);
+            self.output.insert(at, Child::Break(true, None));
+        }
+
         self.output.push(spacing(elem.above.get(styles)));
 
         if !breakable || fr.is_some() {
+            // Labelled unbreakable blocks in columns may still be split if
+            // they don't even fit into a full column, so we prepare a
+            // breakable variant.
+            let split = self.columns.get() > 1 && fr.is_none() && elem.label().is_some();
+            let fallback = split.then(|| {
+                self.boxed(MultiChild {
+                    align,
+                    sticky,
+                    alone,
+                    spanning: None,
+                    elem,
+                    styles,
+                    locator: locator.relayout(),
+                    cell: CachedCell::new(),
+                })
+            });
+
+            let spanning = self.spanning_variant(elem, styles, &locator);
             self.output.push(Child::Single(self.boxed(SingleChild {
                 align,
                 sticky,
                 alone,
                 fr,
+                fallback,
+                spanning,
                 elem,
                 styles,
                 locator,
                 cell: CachedCell::new(),
             })));
         } else {
+            let spanning = self.spanning_variant(elem, styles, &locator);
             self.output.push(Child::Multi(self.boxed(MultiChild {
                 align,
                 sticky,
                 alone,
+                spanning,
                 elem,
                 styles,
                 locator,
@@ -278,6 +364,37 @@ impl<'a> Collector<'a, '_, '_> {
         self.par_situation = ParSituation::Other;
     }
 
+    /// Prepares a variant of a block that spans all columns, in case it is
+    /// wider than all of them. This is handled like a parent-scoped float.
+    fn spanning_variant(
+        &mut self,
+        elem: &'a Packed<BlockElem>,
+        styles: StyleChain<'a>,
+        locator: &Locator<'a>,
+    ) -> Option<BumpBox<'a, PlacedChild<'a>>> {
+        if !self.spanning {
+            return None;
+        }
+
+        let key = typst_utils::hash128(elem);
+        let location = self.locator.next_location(self.engine.introspector, key);
+        Some(self.boxed(PlacedChild {
+            align_x: FixedAlignment::Center,
+            align_y: Smart::Auto,
+            scope: PlacementScope::Parent,
+            float: true,
+            clearance: styles.resolve(PlaceElem::clearance),
+            delta: Axes::splat(Rel::zero()),
+            body: elem.pack_ref(),
+            styles,
+            locator: locator.relayout(),
+            location,
+            alignment: Smart::Auto,
+            wide: true,
+            cell: CachedCell::new(),
+        }))
+    }
+
     /// Collects a placed element into a [`PlacedChild`].
     fn place(
         &mut self,
@@ -323,16 +440,42 @@ impl<'a> Collector<'a, '_, '_> {
             float,
             clearance,
             delta,
-            elem,
+            body: &elem.body,
             styles,
             locator,
+            location: elem.location().unwrap(),
             alignment,
+            wide: false,
             cell: CachedCell::new(),
         })));
 
         Ok(())
     }
 
+    /// Collects pinned content into a [`PinChild`]. The content is laid out
+    /// right away as it doesn't depend on the concrete regions.
+    fn pin(
+        &mut self,
+        elem: &'a Packed<ColumnsPin>,
+        styles: StyleChain<'a>,
+    ) -> SourceResult<()> {
+        let frame = crate::layout_frame(
+            self.engine,
+            &elem.body,
+            self.locator.next(&elem.span()),
+            styles,
+            Region::new(self.base, Axes::new(self.expand, false)),
+        )?;
+
+        self.output.push(Child::Pin(self.boxed(PinChild {
+            column: elem.column.get(styles).get() - 1,
+            clearance: elem.clearance.resolve(styles),
+            frame,
+        })));
+
+        Ok(())
+    }
+
     /// Wraps a value in a bump-allocated box to reduce its footprint in the
     /// [`Child`] enum.
     fn boxed<T>(&self, value: T) -> BumpBox<'a, T> {
@@ -361,8 +504,13 @@ pub enum Child<'a> {
     Placed(BumpBox<'a, PlacedChild<'a>>),
     /// A place flush.
     Flush,
-    /// An explicit column break.
-    Break(bool),
+    /// An explicit column break, which may be weak and target a specific
+    /// column (one-based).
+    Break(bool, Option<Spanned<NonZeroUsize>>),
+    /// A column break opportunity with a penalty.
+    Penalty(i64),
+    /// Content pinned to the top of a column.
+    Pin(BumpBox<'a, PinChild>),
 }
 
 /// A child that encapsulates a layouted line of a paragraph.
@@ -371,6 +519,41 @@ pub struct LineChild {
     pub frame: Frame,
     pub align: Axes<FixedAlignment>,
     pub need: Abs,
+    /// Whether the line ends a sentence and may be followed by a column
+    /// break.
+    pub sentence: bool,
+    /// The index of the line in its paragraph.
+    pub index: usize,
+    /// The number of lines in the paragraph.
+    pub lines: usize,
+}
+
+/// Whether the text of a line ends with sentence-ending punctuation, possibly
+/// followed by closing quotes or brackets.
+fn ends_sentence(frame: &Frame) -> bool {
+    last_text(frame).is_some_and(|text| {
+        text.trim_end()
+            .trim_end_matches(['"', '\'', ')', ']', '”', '’', '»', '›'])
+            .ends_with(['.', '!', '?', '…', '。', '！', '？'])
+    })
+}
+
+/// The last non-blank text in a frame.
+fn last_text(frame: &Frame) -> Option<&str> {
+    frame.items().rev().find_map(|(_, item)| match item {
+        FrameItem::Text(text) if !text.text.trim().is_empty() => Some(text.text.as_str()),
+        FrameItem::Group(group) => last_text(&group.frame),
+        _ => None,
+    })
+}
+
+/// A child that encapsulates content pinned to the top of a column.
+#[derive(Debug)]
+pub struct PinChild {
+    /// The zero-based index of the target column.
+    pub column: usize,
+    pub clearance: Abs,
+    pub frame: Frame,
 }
 
 /// A child that encapsulates a prepared unbreakable block.
@@ -380,6 +563,13 @@ pub struct SingleChild<'a> {
     pub sticky: bool,
     pub alone: bool,
     pub fr: Option<Fr>,
+    /// A breakable variant of the block that is used if the block is too
+    /// large for a full region. Only present for labelled blocks.
+    pub fallback: Option<BumpBox<'a, MultiChild<'a>>>,
+    /// A variant of the block that spans all columns, used if the block is
+    /// wider than all of them. Only present if `columns.wide-child` is
+    /// `"span"`.
+    pub spanning: Option<BumpBox<'a, PlacedChild<'a>>>,
     elem: &'a Packed<BlockElem>,
     styles: StyleChain<'a>,
     locator: Locator<'a>,
@@ -387,6 +577,11 @@ pub struct SingleChild<'a> {
 }
 
 impl SingleChild<'_> {
+    /// The span of the block.
+    pub fn span(&self) -> Span {
+        self.elem.span()
+    }
+
     /// Build the child's frame given the region's base size.
     pub fn layout(&self, engine: &mut Engine, region: Region) -> SourceResult<Frame> {
         self.cell.get_or_init(region, |mut region| {
@@ -445,6 +640,9 @@ pub struct MultiChild<'a> {
     pub align: Axes<FixedAlignment>,
     pub sticky: bool,
     alone: bool,
+    /// A variant of the block that spans all columns. See
+    /// [`SingleChild::spanning`].
+    pub spanning: Option<BumpBox<'a, PlacedChild<'a>>>,
     elem: &'a Packed<BlockElem>,
     styles: StyleChain<'a>,
     locator: Locator<'a>,
@@ -452,6 +650,11 @@ pub struct MultiChild<'a> {
 }
 
 impl<'a> MultiChild<'a> {
+    /// The span of the block.
+    pub fn span(&self) -> Span {
+        self.elem.span()
+    }
+
     /// Build the child's frames given regions.
     pub fn layout<'b>(
         &'b self,
@@ -610,6 +813,11 @@ impl MultiSpill<'_, '_> {
     pub fn align(&self) -> Axes<FixedAlignment> {
         self.multi.align
     }
+
+    /// The span of the breakable block.
+    pub fn span(&self) -> Span {
+        self.multi.span()
+    }
 }
 
 /// A child that encapsulates a prepared placed element.
@@ -621,10 +829,15 @@ pub struct PlacedChild<'a> {
     pub float: bool,
     pub clearance: Abs,
     pub delta: Axes<Rel<Abs>>,
-    elem: &'a Packed<PlaceElem>,
+    body: &'a Content,
     styles: StyleChain<'a>,
     locator: Locator<'a>,
+    location: Location,
     alignment: Smart<Alignment>,
+    /// Whether this is a block that spans all columns because it is too wide
+    /// for them. Such a block is scaled down to fit into its base and, unlike
+    /// a placed element, isn't a parent of its contents.
+    wide: bool,
     cell: CachedCell<SourceResult<Frame>>,
 }
 
@@ -634,20 +847,25 @@ impl PlacedChild<'_> {
         self.cell.get_or_init(base, |base| {
             let align = self.alignment.unwrap_or_else(|| Alignment::CENTER);
             let aligned = AlignElem::alignment.set(align).wrap();
-            let styles = self.styles.chain(&aligned);
+
+            // A spanning block keeps its own alignment.
+            let styles =
+                if self.wide { self.styles } else { self.styles.chain(&aligned) };
 
             let mut frame = layout_and_modify(styles, |styles| {
                 crate::layout_frame(
                     engine,
-                    &self.elem.body,
+                    self.body,
                     self.locator.relayout(),
                     styles,
                     Region::new(base, Axes::splat(false)),
                 )
             })?;
 
-            if self.float {
-                frame.set_parent(self.elem.location().unwrap());
+            if self.wide && frame.width() > base.x {
+                shrink_to_width(&mut frame, base.x);
+            } else if self.float && !self.wide {
+                frame.set_parent(self.location);
             }
 
             Ok(frame)
@@ -656,10 +874,18 @@ impl PlacedChild<'_> {
 
     /// The element's location.
     pub fn location(&self) -> Location {
-        self.elem.location().unwrap()
+        self.location
     }
 }
 
+/// Scales a frame down uniformly so that it has the given width.
+pub fn shrink_to_width(frame: &mut Frame, width: Abs) {
+    let scale = Ratio::new(width / frame.width());
+    let size = frame.size() * scale.get();
+    frame.transform(Transform::scale(scale, scale));
+    frame.set_size(size);
+}
+
 /// Wraps a parameterized computation and caches its latest output.
 ///
 /// - When the computation is performed multiple times consecutively with the
@@ -8,8 +8,8 @@ use typst_library::introspection::{
     SplitLocator, Tag,
 };
 use typst_library::layout::{
-    Abs, Axes, Dir, FixedAlignment, Fragment, Frame, FrameItem, OuterHAlignment,
-    PlacementScope, Point, Region, Regions, Rel, Size,
+    Abs, Axes, ColumnsMarker, Dir, FixedAlignment, Fragment, Frame, FrameItem,
+    OuterHAlignment, PlacementScope, Point, Region, Regions, Rel, Size,
 };
 use typst_library::model::{
     FootnoteElem, FootnoteEntry, LineNumberingScope, Numbering, ParLineMarker,
@@ -18,7 +18,8 @@ use typst_syntax::Span;
 use typst_utils::{NonZeroExt, Numeric};
 
 use super::{
-    Config, FlowMode, FlowResult, LineNumberConfig, PlacedChild, Stop, Work, distribute,
+    Config, FlowMode, FlowResult, LineNumberConfig, PinChild, PlacedChild, Stop, Work,
+    distribute,
 };
 
 /// Composes the contents of a single page/region. A region can have multiple
@@ -64,7 +65,7 @@ pub struct Composer<'a, 'b, 'x, 'y> {
     pub engine: &'x mut Engine<'y>,
     pub work: &'x mut Work<'a, 'b>,
     pub config: &'x Config<'x>,
-    column: usize,
+    pub column: usize,
     page_base: Size,
     page_insertions: Insertions<'a, 'b>,
     column_insertions: Insertions<'a, 'b>,
@@ -106,66 +107,119 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
 
     /// Lay out the inner contents of a container/page.
     fn page_contents(&mut self, locator: Locator, regions: Regions) -> FlowResult<Frame> {
-        // No point in create column regions, if there's just one!
-        if self.config.columns.count == 1 {
+        // No point in create column regions, if there's just one! Unless it
+        // needs a marker.
+        if self.config.columns.count == 1 && !self.config.markers {
             return self.column(locator, regions);
         }
 
         // Create a backlog for multi-column layout.
+        let config = self.config;
+        let columns = &config.columns;
         let column_height = regions.size.y;
-        let backlog: Vec<_> = std::iter::once(&column_height)
-            .chain(regions.backlog)
-            .flat_map(|&h| std::iter::repeat_n(h, self.config.columns.count))
-            .skip(1)
-            .collect();
+        let mut backlog =
+            Vec::with_capacity(regions.backlog.len() * columns.fill + columns.fill);
+        backlog.extend((1..columns.fill).map(|i| columns.height(i, column_height)));
+        for &h in regions.backlog {
+            backlog.extend((0..columns.fill).map(|i| columns.height(i, h)));
+        }
 
-        // Subregions for column layout.
+        // Subregions for column layout. Once the backlog is exhausted, the
+        // next subregion is the first column of a region of the last height.
         let mut inner = Regions {
-            size: Size::new(self.config.columns.width, column_height),
+            size: Size::new(columns.width, columns.height(0, column_height)),
             backlog: &backlog,
-            expand: Axes::new(true, regions.expand.y),
+            last: regions.last.map(|h| columns.height(0, h)),
+            expand: Axes::new(!config.shrink_wrap, regions.expand.y),
             ..regions
         };
 
-        // The size of the merged frame hosting multiple columns.
-        let size = Size::new(
-            regions.size.x,
-            if regions.expand.y { regions.size.y } else { Abs::zero() },
-        );
-
-        let mut output = Frame::hard(size);
-        let mut offset = Abs::zero();
         let mut locator = locator.split();
 
-        // Lay out the columns and stitch them together.
-        for i in 0..self.config.columns.count {
+        // Lay out the columns first. Columns beyond the fill cap stay empty.
+        let mut frames = Vec::with_capacity(columns.fill);
+        for i in 0..columns.fill {
             self.column = i;
-            let frame = self.column(locator.next(&()), inner)?;
+            inner.size.x = columns.width_of(i);
+            frames.push(self.column(locator.next(&()), inner)?);
+            inner.next();
+        }
 
-            if !regions.expand.y {
-                output.size_mut().y.set_max(frame.height());
-            }
+        // The size of the merged frame hosting multiple columns, computed
+        // once from the tallest column.
+        let height = if regions.expand.y {
+            regions.size.y
+        } else {
+            frames.iter().map(Frame::height).max().unwrap_or_default()
+        };
+
+        let mut output = Frame::hard(Size::new(regions.size.x, height));
+        let mut offset = Abs::zero();
 
+        // Stitch the columns together.
+        for (i, frame) in frames.into_iter().enumerate() {
             let width = frame.width();
             let x = if self.config.columns.dir == Dir::LTR {
                 offset
             } else {
                 regions.size.x - offset - width
             };
-            offset += width + self.config.columns.gutter;
+            offset += width + self.config.columns.gutter_after(i);
+
+            if self.config.markers {
+                self.mark_column(&mut output, &mut locator, i, Point::with_x(x), &frame);
+            }
 
             output.push_frame(Point::with_x(x), frame);
-            inner.next();
         }
 
         Ok(output)
     }
 
+    /// Adds a marker for the column with the given index and frame at the
+    /// given position to the output.
+    fn mark_column(
+        &mut self,
+        output: &mut Frame,
+        locator: &mut SplitLocator,
+        index: usize,
+        pos: Point,
+        frame: &Frame,
+    ) {
+        // Like line markers, the marker is only ever found through
+        // introspection, so it just needs a location and no realization.
+        let mut marker = Packed::new(ColumnsMarker::new(
+            NonZeroUsize::new(index + 1).unwrap(),
+            frame.width().into(),
+            frame.height().into(),
+        ));
+        let key = typst_utils::hash128(&marker);
+        let loc = locator.next_location(self.engine.introspector, key);
+        marker.set_location(loc);
+        output.push(pos, FrameItem::Tag(Tag::Start(marker.pack())));
+        output.push(pos, FrameItem::Tag(Tag::End(loc, key)));
+    }
+
     /// Lay out a column, including column insertions.
     fn column(&mut self, locator: Locator, regions: Regions) -> FlowResult<Frame> {
         // Reset column insertion when starting a new column.
         self.column_insertions = Insertions::default();
 
+        // Place pinned content at the top of its column. Pins targeting a
+        // column that doesn't exist or stays empty go to the last filled one.
+        if !self.work.pins.is_empty() {
+            let last = self.config.columns.fill - 1;
+            let column = self.column;
+            let (pins, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut self.work.pins)
+                .iter()
+                .copied()
+                .partition(|pin| pin.column.min(last) == column);
+            self.work.pins = rest.into_iter().collect();
+            for pin in pins {
+                self.column_insertions.push_pin(pin);
+            }
+        }
+
         // Process footnote spill.
         if let Some(spill) = self.work.footnote_spill.take() {
             self.footnote_spill(spill, regions.base())?;
@@ -617,6 +671,7 @@ fn layout_footnote(
 /// An additive list of insertions.
 #[derive(Default)]
 struct Insertions<'a, 'b> {
+    pins: Vec<&'b PinChild>,
     top_floats: Vec<(&'b PlacedChild<'a>, Frame)>,
     bottom_floats: Vec<(&'b PlacedChild<'a>, Frame)>,
     footnotes: Vec<Frame>,
@@ -628,6 +683,13 @@ struct Insertions<'a, 'b> {
 }
 
 impl<'a, 'b> Insertions<'a, 'b> {
+    /// Add pinned content to the top area, above the floats.
+    fn push_pin(&mut self, pin: &'b PinChild) {
+        self.width.set_max(pin.frame.width());
+        self.top_size += pin.frame.height() + pin.clearance;
+        self.pins.push(pin);
+    }
+
     /// Add a float to the top or bottom area.
     fn push_float(
         &mut self,
@@ -675,7 +737,8 @@ impl<'a, 'b> Insertions<'a, 'b> {
     fn finalize(self, work: &mut Work, config: &Config, inner: Frame) -> Frame {
         work.extend_skips(&self.skips);
 
-        if self.top_floats.is_empty()
+        if self.pins.is_empty()
+            && self.top_floats.is_empty()
             && self.bottom_floats.is_empty()
             && self.footnote_separator.is_none()
             && self.footnotes.is_empty()
@@ -689,6 +752,11 @@ impl<'a, 'b> Insertions<'a, 'b> {
         let mut offset_top = Abs::zero();
         let mut offset_bottom = size.y - self.bottom_size;
 
+        for pin in self.pins {
+            output.push_frame(Point::with_y(offset_top), pin.frame.clone());
+            offset_top += pin.frame.height() + pin.clearance;
+        }
+
         for (placed, frame) in self.top_floats {
             let x = placed.align_x.position(size.x - frame.width());
             let y = offset_top;
@@ -1,14 +1,27 @@
+use std::num::NonZeroUsize;
+
+use ecow::eco_vec;
+use typst_library::diag::warning;
+use typst_library::foundations::Repr;
 use typst_library::introspection::Tag;
 use typst_library::layout::{
-    Abs, Axes, FixedAlignment, Fr, Frame, FrameItem, Point, Region, Regions, Rel, Size,
+    Abs, Axes, ColpenaltyElem, FixedAlignment, Fr, Frame, FrameItem, Point, Region,
+    Regions, Rel, Size, SpaceDistribution, WideChild,
 };
+use typst_syntax::{Span, Spanned};
 use typst_utils::Numeric;
 
+use super::collect::missing_column;
 use super::{
     Child, Composer, FlowResult, LineChild, MultiChild, MultiSpill, PlacedChild,
-    SingleChild, Stop, Work,
+    SingleChild, Stop, Work, shrink_to_width,
 };
 
+/// The penalty for a column break after a line that ends a sentence if
+/// `prefer_sentence_breaks` is enabled. Breaking there instead of at the
+/// natural break point is cheaper as long as it leaves at most 20pt empty.
+const SENTENCE_BREAK_PENALTY: i64 = -20;
+
 /// Distributes as many children as fit from `composer.work` into the first
 /// region and returns the resulting frame.
 pub fn distribute(composer: &mut Composer, regions: Regions) -> FlowResult<Frame> {
@@ -18,6 +31,8 @@ pub fn distribute(composer: &mut Composer, regions: Regions) -> FlowResult<Frame
         items: vec![],
         sticky: None,
         stickable: None,
+        candidates: vec![],
+        empty_lines: None,
     };
     let init = distributor.snapshot();
     let forced = match distributor.run() {
@@ -62,6 +77,11 @@ struct Distributor<'a, 'b, 'x, 'y, 'z> {
     /// blocks are supposed to always be in the same page as the subsequent
     /// frame, but that is impossible in that case, which is thus pathological.
     stickable: Option<bool>,
+    /// Break opportunities marked by column penalties in this region.
+    candidates: Vec<Candidate<'a, 'b>>,
+    /// If the items end with a run of empty lines, the number of items before
+    /// that run. Used to trim the lines at the end of the region.
+    empty_lines: Option<usize>,
 }
 
 /// A snapshot of the distribution state.
@@ -70,6 +90,16 @@ struct DistributionSnapshot<'a, 'b> {
     items: usize,
 }
 
+/// A break opportunity marked by a column penalty.
+struct Candidate<'a, 'b> {
+    /// The state to restore when breaking here.
+    snapshot: DistributionSnapshot<'a, 'b>,
+    /// The penalty for breaking here.
+    penalty: i64,
+    /// The space that was remaining in the region at this point.
+    remaining: Abs,
+}
+
 /// A laid out item in a distribution.
 enum Item<'a, 'b> {
     /// An introspection tag.
@@ -105,6 +135,14 @@ impl Item<'_, '_> {
 impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
     /// Distributes content into the region.
     fn run(&mut self) -> FlowResult<()> {
+        // Leave this column empty if a column break targets another one.
+        if let Some(target) = self.composer.work.column {
+            if target != self.composer.column {
+                return Err(Stop::Finish(true));
+            }
+            self.composer.work.column = None;
+        }
+
         // First, handle spill of a breakable block.
         if let Some(spill) = self.composer.work.spill.take() {
             self.multi_spill(spill)?;
@@ -137,7 +175,10 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             Child::Multi(multi) => self.multi(multi)?,
             Child::Placed(placed) => self.placed(placed)?,
             Child::Flush => self.flush()?,
-            Child::Break(weak) => self.break_(*weak)?,
+            Child::Break(weak, to) => self.break_(*weak, *to)?,
+            Child::Penalty(penalty) => self.penalty(*penalty)?,
+            // Pinned content is handled by the composer.
+            Child::Pin(_) => {}
         }
         Ok(())
     }
@@ -156,9 +197,21 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         }
     }
 
+    /// Whether the region contains in-flow content, i.e. lines or blocks.
+    fn has_in_flow(&self) -> bool {
+        self.items.iter().any(|item| matches!(item, Item::Frame(..)))
+    }
+
     /// Processes relative spacing.
     fn rel(&mut self, amount: Rel<Abs>, weakness: u8) {
-        let amount = amount.relative_to(self.regions.base().y);
+        let mut amount = amount.relative_to(self.regions.base().y);
+
+        // Automatic spacing (block, paragraph, and line spacing) may be shrunk
+        // to avoid a single trailing region.
+        if weakness >= 3 {
+            amount *= 1.0 - self.composer.config.shrink.get();
+        }
+
         if weakness > 0 && !self.keep_spacing(amount, weakness) {
             return;
         }
@@ -224,6 +277,19 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
 
     /// Processes a line of a paragraph.
     fn line(&mut self, line: &'b LineChild) -> FlowResult<()> {
+        // When trimming, drop empty lines at the start of the region.
+        let empty = self.composer.config.trim && line.frame.is_empty();
+        if empty && !self.has_in_flow() {
+            return Ok(());
+        }
+
+        // Move the first line of the column down onto the baseline grid.
+        if let Some(pitch) = self.composer.config.baseline_grid
+            && !self.has_in_flow()
+        {
+            self.snap_to_grid(pitch, line.frame.baseline());
+        }
+
         // If the line doesn't fit and a followup region may improve things,
         // finish the region.
         if !self.regions.size.y.fits(line.frame.height()) && self.regions.may_progress() {
@@ -244,17 +310,90 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             return Err(Stop::Finish(false));
         }
 
-        self.frame(line.frame.clone(), line.align, false, false)
+        let start = self.empty_lines.unwrap_or(self.items.len());
+        self.frame(line.frame.clone(), line.align, false, false)?;
+        if empty {
+            self.empty_lines = Some(start);
+        }
+
+        let mut penalty = self.line_penalty(line);
+        if line.sentence && self.composer.config.prefer_sentence_breaks {
+            penalty = Some(penalty.unwrap_or(0).saturating_add(SENTENCE_BREAK_PENALTY));
+        }
+
+        if let Some(penalty) = penalty {
+            self.candidate(penalty);
+        }
+
+        Ok(())
+    }
+
+    /// The penalty for a column break after a line, based on the widow,
+    /// orphan, and column break penalties. Returns `None` if all of them are
+    /// zero, in which case lines aren't break opportunities.
+    fn line_penalty(&self, line: &LineChild) -> Option<i64> {
+        let config = &self.composer.config;
+        if config.widow_penalty == 0
+            && config.orphan_penalty == 0
+            && config.colbreak_penalty == 0
+        {
+            return None;
+        }
+
+        // A break after the last line is one between paragraphs.
+        if line.index + 1 == line.lines {
+            return Some(0);
+        }
+
+        let mut penalty = config.colbreak_penalty;
+        if line.index == 0 {
+            penalty = penalty.saturating_add(config.orphan_penalty);
+        }
+        if line.index + 2 == line.lines {
+            penalty = penalty.saturating_add(config.widow_penalty);
+        }
+        Some(penalty)
+    }
+
+    /// Adds the spacing that moves a baseline at the given offset below the
+    /// current position onto the next line of the baseline grid with the
+    /// given pitch.
+    fn snap_to_grid(&mut self, pitch: Abs, baseline: Abs) {
+        let top: Abs = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Abs(amount, _) => Some(*amount),
+                _ => None,
+            })
+            .sum();
+
+        let mut shift = (self.composer.config.grid_origin - top - baseline) % pitch;
+        if shift < Abs::zero() {
+            shift += pitch;
+        }
+
+        if !shift.is_zero() {
+            self.regions.size.y -= shift;
+            self.items.push(Item::Abs(shift, 0));
+        }
     }
 
     /// Processes an unbreakable block.
     fn single(&mut self, single: &'b SingleChild<'a>) -> FlowResult<()> {
         // Lay out the block.
-        let frame = single.layout(
+        let mut frame = single.layout(
             self.composer.engine,
             Region::new(self.regions.base(), self.regions.expand),
         )?;
 
+        // Handle blocks that are wider than all columns.
+        if self.wide(&mut frame, single.span())
+            && let Some(spanning) = &single.spanning
+        {
+            return self.placed(spanning);
+        }
+
         // Handle fractionally sized blocks.
         if let Some(fr) = single.fr {
             self.composer
@@ -270,6 +409,19 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             return Err(Stop::Finish(false));
         }
 
+        // If a labelled block doesn't even fit into a full region, we split it
+        // after all instead of letting it overflow.
+        if let Some(multi) = &single.fallback
+            && !self.regions.full.fits(frame.height())
+        {
+            self.composer.engine.sink.warn(warning!(
+                multi.span(),
+                "unbreakable block is taller than a full region and was split";
+                hint: "try reducing the block's height or making it breakable",
+            ));
+            return self.multi(multi);
+        }
+
         self.frame(frame, single.align, single.sticky, false)
     }
 
@@ -282,7 +434,12 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         }
 
         // Lay out the block.
-        let (frame, spill) = multi.layout(self.composer.engine, self.regions)?;
+        let (mut frame, spill) = multi.layout(self.composer.engine, self.regions)?;
+        if self.wide(&mut frame, multi.span())
+            && let Some(spanning) = &multi.spanning
+        {
+            return self.placed(spanning);
+        }
         self.frame(frame, multi.align, multi.sticky, true)?;
 
         // If the block didn't fully fit into the current region, save it into
@@ -306,7 +463,9 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
 
         // Lay out the spilled remains.
         let align = spill.align();
-        let (frame, spill) = spill.layout(self.composer.engine, self.regions)?;
+        let span = spill.span();
+        let (mut frame, spill) = spill.layout(self.composer.engine, self.regions)?;
+        self.wide(&mut frame, span);
         self.frame(frame, align, false, true)?;
 
         // If there's still more, save it into the `spill` and finish the
@@ -319,6 +478,54 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         Ok(())
     }
 
+    /// Handles a frame of a block that is wider than all columns combined, as
+    /// configured by `columns.wide-child`. Returns `true` if the block should
+    /// span all columns instead.
+    fn wide(&mut self, frame: &mut Frame, span: Span) -> bool {
+        let columns = &self.composer.config.columns;
+        if columns.count <= 1 {
+            return false;
+        }
+
+        if frame.width() <= columns.total() {
+            self.overflow(frame, span);
+            return false;
+        }
+
+        match self.composer.config.wide_child {
+            WideChild::Overflow => {
+                self.composer.engine.sink.warn(warning!(
+                    span,
+                    "block is wider than all columns combined";
+                    hint: "set `columns.wide-child` to `\"shrink\"` or `\"span\"` to make it fit",
+                ));
+                false
+            }
+            WideChild::Shrink => {
+                shrink_to_width(frame, self.regions.size.x);
+                false
+            }
+            WideChild::Span => true,
+        }
+    }
+
+    /// Warns if a block is wider than the column it ends up in and thus
+    /// overflows into the gutter or the next column.
+    fn overflow(&mut self, frame: &Frame, span: Span) {
+        let width = self.regions.size.x;
+        if width.fits(frame.width()) {
+            return;
+        }
+
+        self.composer.engine.sink.warn(warning!(
+            span,
+            "block overflows column {} by {}",
+            self.composer.column + 1,
+            (frame.width() - width).repr();
+            hint: "try reducing the block's width or making the column wider",
+        ));
+    }
+
     /// Processes an in-flow frame, generated from a line or block.
     fn frame(
         &mut self,
@@ -327,6 +534,8 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         sticky: bool,
         breakable: bool,
     ) -> FlowResult<()> {
+        self.empty_lines = None;
+
         if sticky {
             // If the frame is sticky and we haven't remembered a preceding
             // sticky element, make a checkpoint which we can restore should we
@@ -384,12 +593,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             // ends up at a break due to the float.
             let weak_spacing = self.weak_spacing();
             self.regions.size.y += weak_spacing;
-            self.composer.float(
-                placed,
-                &self.regions,
-                self.items.iter().any(|item| matches!(item, Item::Frame(..))),
-                true,
-            )?;
+            self.composer.float(placed, &self.regions, self.has_in_flow(), true)?;
             self.regions.size.y -= weak_spacing;
         } else {
             let frame = placed.layout(self.composer.engine, self.regions.base())?;
@@ -412,17 +616,101 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
     }
 
     /// Processes a column break.
-    fn break_(&mut self, weak: bool) -> FlowResult<()> {
-        // If there is a region to break into, break into it.
-        if (!weak || !self.items.is_empty())
+    fn break_(
+        &mut self,
+        weak: bool,
+        to: Option<Spanned<NonZeroUsize>>,
+    ) -> FlowResult<()> {
+        let columns = &self.composer.config.columns;
+        if let Some(to) = to
+            && to.v.get() > columns.count
+        {
+            return Err(eco_vec![missing_column(to, columns.count)].into());
+        }
+
+        // If there is a region to break into, break into it. A weak break at
+        // the top of a column, where there is no in-flow content yet, does
+        // nothing. Tags, spacing, and absolutely placed content don't count.
+        if (!weak || self.has_in_flow())
             && (!self.regions.backlog.is_empty() || self.regions.last.is_some())
         {
+            // Columns beyond the fill cap stay empty, so we can't target them.
+            self.composer.work.column =
+                to.map(|to| (to.v.get() - 1).min(columns.fill.saturating_sub(1)));
             self.composer.work.advance();
             return Err(Stop::Finish(true));
         }
         Ok(())
     }
 
+    /// Processes a column penalty.
+    fn penalty(&mut self, penalty: i64) -> FlowResult<()> {
+        if penalty <= ColpenaltyElem::FORCE {
+            return self.break_(false, None);
+        }
+
+        self.candidate(penalty);
+        Ok(())
+    }
+
+    /// Registers a break opportunity with a penalty after the child that is
+    /// currently being processed.
+    fn candidate(&mut self, penalty: i64) {
+        // Breaking before any in-flow content would just produce an empty
+        // region, so there is no opportunity here.
+        if self.has_in_flow() {
+            // The child is only marked as processed once it returns, so the
+            // snapshot must skip it. Otherwise, it would be processed again
+            // after restoring.
+            let mut snapshot = self.snapshot();
+            snapshot.work.advance();
+            self.candidates.push(Candidate {
+                snapshot,
+                penalty,
+                remaining: self.regions.size.y,
+            });
+        }
+    }
+
+    /// Picks the cheapest break opportunity from the column penalties if it
+    /// is cheaper than breaking at the natural break point, i.e. the current
+    /// state.
+    fn cheapest_candidate(&mut self) -> Option<DistributionSnapshot<'a, 'b>> {
+        // If the natural break point is directly at a penalty, it inherits
+        // that penalty. Weak spacing and tags after the penalty don't matter
+        // since they are trimmed or moved along at the break anyway.
+        let natural = self
+            .candidates
+            .last()
+            .filter(|candidate| {
+                self.items.get(candidate.snapshot.items..).is_some_and(|rest| {
+                    rest.iter()
+                        .all(|item| matches!(item, Item::Abs(_, 1..) | Item::Tag(_)))
+                })
+            })
+            .map_or(0.0, |candidate| candidate.penalty as f64);
+
+        // Breaking earlier costs the space that is left empty in addition to
+        // the penalty. Trailing weak spacing is trimmed at the natural break,
+        // so it doesn't count as used.
+        let remaining = self.regions.size.y + self.weak_spacing();
+        let cost = |candidate: &Candidate| {
+            candidate.penalty as f64 + (candidate.remaining - remaining).to_pt()
+        };
+
+        let (index, cheapest) = self
+            .candidates
+            .iter()
+            .map(cost)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if cheapest >= natural {
+            return None;
+        }
+
+        Some(self.candidates.swap_remove(index).snapshot)
+    }
+
     /// Arranges the produced items into an output frame.
     ///
     /// This performs alignment and resolves fractional spacing and blocks.
@@ -438,6 +726,10 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         } else if !self.items.is_empty() && self.items.iter().all(Item::migratable) {
             // Restore the initial state of all items are migratable.
             self.restore(init);
+        } else if let Some(snapshot) = self.cheapest_candidate() {
+            // If a column penalty makes an earlier break cheaper, break there
+            // instead.
+            self.restore(snapshot);
         } else {
             // If we ended on a sticky block, but are not yet at the end of
             // the flow, restore the saved checkpoint to move the sticky
@@ -447,6 +739,19 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             }
         }
 
+        // When trimming, drop empty lines at the end of the region, unless the
+        // flow ends here. Tags and placed items are kept.
+        if let Some(start) = self.empty_lines.filter(|&start| start <= self.items.len())
+            && !self.composer.work.done()
+        {
+            let trimmed = self.items.split_off(start);
+            self.items.extend(
+                trimmed
+                    .into_iter()
+                    .filter(|item| matches!(item, Item::Tag(_) | Item::Placed(..))),
+            );
+        }
+
         self.trim_spacing();
 
         let mut frs = Fr::zero();
@@ -496,23 +801,51 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
 
         // Determine the region's size.
         let size = region.expand.select(region.size, used.min(region.size));
-        let free = size.y - used.y;
+        let mut free = size.y - used.y;
+
+        // Distribute the remaining space. Fractional spacing already took it
+        // up, so this only applies without it.
+        let mut offset = Abs::zero();
+        let mut gap = Abs::zero();
+        if free > Abs::zero() {
+            match self.composer.config.space_distribution {
+                SpaceDistribution::Bottom => {}
+                SpaceDistribution::Top => {
+                    offset = free;
+                    free = Abs::zero();
+                }
+                SpaceDistribution::Between => {
+                    let gaps = self.block_gaps();
+                    if gaps > 0 {
+                        gap = free / gaps as f64;
+                        free = Abs::zero();
+                    }
+                }
+            }
+        }
 
         let mut output = Frame::soft(size);
         let mut ruler = FixedAlignment::Start;
-        let mut offset = Abs::zero();
         let mut fr_frames = fr_frames.into_iter();
+        let mut seen_frame = false;
+        let mut separated = false;
 
         // Position all items.
         for item in self.items {
             match item {
                 Item::Tag(tag) => {
-                    let y = offset + ruler.position(free);
+                    // Tags after the spacing in front of a block belong to
+                    // that block, so they move along with the gap.
+                    let pending = if seen_frame && separated { gap } else { Abs::zero() };
+                    let y = offset + pending + ruler.position(free);
                     let pos = Point::with_y(y);
                     output.push(pos, FrameItem::Tag(tag.clone()));
                 }
-                Item::Abs(v, _) => {
+                Item::Abs(v, weakness) => {
                     offset += v;
+                    // Line spacing has weakness 5 and doesn't separate
+                    // blocks.
+                    separated |= weakness < 5;
                 }
                 Item::Fr(v, single) => {
                     let length = v.share(frs, fr_space);
@@ -525,6 +858,11 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                     offset += length;
                 }
                 Item::Frame(frame, align) => {
+                    if seen_frame && separated {
+                        offset += gap;
+                    }
+                    seen_frame = true;
+                    separated = false;
                     ruler = ruler.max(align.y);
 
                     let x = align.x.position(size.x - frame.width());
@@ -552,6 +890,27 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         Ok(output)
     }
 
+    /// The number of gaps between consecutive frames that are separated by
+    /// something other than line spacing, i.e. the gaps between blocks and
+    /// paragraphs.
+    fn block_gaps(&self) -> usize {
+        let mut gaps = 0;
+        let mut seen_frame = false;
+        let mut separated = false;
+        for item in &self.items {
+            match item {
+                Item::Abs(_, weakness) => separated |= *weakness < 5,
+                Item::Frame(..) => {
+                    gaps += (seen_frame && separated) as usize;
+                    seen_frame = true;
+                    separated = false;
+                }
+                _ => {}
+            }
+        }
+        gaps
+    }
+
     /// Create a snapshot of the work and items.
     fn snapshot(&self) -> DistributionSnapshot<'a, 'b> {
         DistributionSnapshot {
@@ -0,0 +1,204 @@
+use typst_library::diag::SourceResult;
+use typst_library::engine::Engine;
+use typst_library::foundations::{Packed, StyleChain};
+use typst_library::introspection::{Locator, Tag, TagElem};
+use typst_library::layout::{
+    Abs, Axes, ColumnsElem, FillOrder, Fragment, Frame, FrameItem, Point, Region,
+    Regions, Size, VElem,
+};
+use typst_library::model::ParElem;
+use typst_library::routines::{Arenas, FragmentKind, RealizationKind};
+
+use super::{ColumnConfig, column_count};
+
+/// Lays out the body of a columns element in masonry style or row by row.
+///
+/// Each top-level child is laid out on its own and then placed at the bottom
+/// of the currently shortest column or, in row order, in the next column of
+/// the current row. When the next child does not fit, the region is finished.
+pub fn layout_masonry(
+    elem: &Packed<ColumnsElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    mut regions: Regions,
+) -> SourceResult<Fragment> {
+    let mut locator = locator.split();
+    let mut kind = FragmentKind::Block;
+    let arenas = Arenas::default();
+    let children = (engine.routines.realize)(
+        RealizationKind::LayoutFragment { kind: &mut kind },
+        engine,
+        &mut locator,
+        &arenas,
+        &elem.body,
+        styles,
+    )?;
+
+    let config = ColumnConfig::new(
+        styles,
+        regions,
+        column_count(elem, styles, regions),
+        elem.gutter.resolve(styles),
+    );
+    let continuation = elem.gutter_continuation.resolve(styles).custom().map(|gutter| {
+        ColumnConfig::new(styles, regions, column_count(elem, styles, regions), gutter)
+    });
+    let gap = styles.resolve(ParElem::spacing);
+    let order = elem.fill_order.get(styles);
+    let pod = Region::new(Size::new(config.width, Abs::inf()), Axes::new(true, false));
+
+    // Inline-level content forms a single paragraph, so there is only one
+    // item to place.
+    if kind == FragmentKind::Inline {
+        let frame = crate::layout_frame(
+            engine,
+            &elem.body,
+            locator.next(&elem.span()),
+            styles,
+            pod,
+        )?;
+        let mut masonry = Masonry::new(&config, regions, order);
+        masonry.place(frame, gap);
+        return Ok(Fragment::frame(masonry.finish()));
+    }
+
+    let mut masonry = Masonry::new(&config, regions, order);
+    let mut finished = vec![];
+    let mut pending = vec![];
+
+    for &(child, styles) in &children {
+        if let Some(elem) = child.to_packed::<TagElem>() {
+            // End tags belong to the previous item. Start tags belong to the
+            // next one.
+            match &elem.tag {
+                Tag::End(..) if masonry.attach(&elem.tag) => {}
+                tag => pending.push(tag.clone()),
+            }
+            continue;
+        }
+
+        // The masonry layout has its own spacing between the items.
+        if child.is::<VElem>() {
+            continue;
+        }
+
+        let mut frame =
+            crate::layout_frame(engine, child, locator.next(&child.span()), styles, pod)?;
+        frame.prepend_multiple(
+            pending.drain(..).map(|tag| (Point::zero(), FrameItem::Tag(tag))),
+        );
+
+        if !masonry.fits(&frame, gap) && regions.may_progress() {
+            finished.push(masonry.finish());
+            regions.next();
+            masonry =
+                Masonry::new(continuation.as_ref().unwrap_or(&config), regions, order);
+        }
+
+        masonry.place(frame, gap);
+    }
+
+    let mut last = masonry.finish();
+    last.push_multiple(
+        pending.into_iter().map(|tag| (Point::zero(), FrameItem::Tag(tag))),
+    );
+    finished.push(last);
+
+    // Like the flow, fill up the remaining regions if we should expand.
+    while regions.expand.y && !regions.backlog.is_empty() {
+        regions.next();
+        finished.push(Frame::hard(regions.size));
+    }
+
+    Ok(Fragment::frames(finished))
+}
+
+/// The state of masonry layout within a single region.
+struct Masonry<'c> {
+    config: &'c ColumnConfig,
+    /// The size of the region.
+    size: Size,
+    /// Whether to expand vertically.
+    expand: bool,
+    /// Whether to fill the columns row by row instead of placing each item
+    /// in the shortest column.
+    row: bool,
+    /// The filled height of each column.
+    heights: Vec<Abs>,
+    /// The items placed so far: The column index, the vertical offset and the
+    /// frame.
+    items: Vec<(usize, Abs, Frame)>,
+}
+
+impl<'c> Masonry<'c> {
+    /// Start a new region.
+    fn new(config: &'c ColumnConfig, regions: Regions, order: FillOrder) -> Self {
+        Self {
+            config,
+            size: regions.size,
+            expand: regions.expand.y,
+            row: order == FillOrder::Row,
+            heights: vec![Abs::zero(); config.count],
+            items: vec![],
+        }
+    }
+
+    /// The index of the column in which the next item would be placed and the
+    /// offset at which it would be placed in it.
+    fn next(&self, gap: Abs) -> (usize, Abs) {
+        // In row order, a new row starts below the tallest item of the
+        // previous one.
+        if self.row {
+            let index = self.items.len() % self.config.count;
+            if index > 0 {
+                return (index, self.items[self.items.len() - index].1);
+            }
+            let bottom = self.heights.iter().copied().max().unwrap_or_default();
+            return (0, if self.items.is_empty() { bottom } else { bottom + gap });
+        }
+
+        let (index, &height) = self
+            .heights
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, height)| *height)
+            .unwrap();
+        let occupied = self.items.iter().any(|&(column, ..)| column == index);
+        (index, if occupied { height + gap } else { height })
+    }
+
+    /// Whether the frame would fit at the next position.
+    fn fits(&self, frame: &Frame, gap: Abs) -> bool {
+        let (_, y) = self.next(gap);
+        self.items.is_empty() || self.size.y.fits(y + frame.height())
+    }
+
+    /// Place a frame at the next position.
+    fn place(&mut self, frame: Frame, gap: Abs) {
+        let (index, y) = self.next(gap);
+        self.heights[index] = y + frame.height();
+        self.items.push((index, y, frame));
+    }
+
+    /// Attach a tag to the end of the most recently placed item. Returns
+    /// `false` if there is no such item.
+    fn attach(&mut self, tag: &Tag) -> bool {
+        let Some((.., frame)) = self.items.last_mut() else { return false };
+        let pos = Point::with_y(frame.height());
+        frame.push(pos, FrameItem::Tag(tag.clone()));
+        true
+    }
+
+    /// Stitch the columns together into a frame for the region.
+    fn finish(self) -> Frame {
+        let used = self.heights.iter().copied().max().unwrap_or_default();
+        let height = if self.expand { self.size.y } else { used.min(self.size.y) };
+        let mut output = Frame::hard(Size::new(self.size.x, height));
+        for (index, y, frame) in self.items {
+            let x = self.config.offset(index, self.size.x);
+            output.push_frame(Point::new(x, y), frame);
+        }
+        output
+    }
+}
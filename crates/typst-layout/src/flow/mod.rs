@@ -4,38 +4,53 @@ mod block;
 mod collect;
 mod compose;
 mod distribute;
+mod masonry;
 
 pub(crate) use self::block::unbreakable_pod;
 
 use std::num::NonZeroUsize;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use bumpalo::Bump;
 use comemo::{Track, Tracked, TrackedMut};
 use ecow::EcoVec;
 use rustc_hash::FxHashSet;
 use typst_library::World;
-use typst_library::diag::{At, SourceDiagnostic, SourceResult, bail};
+use typst_library::diag::{At, SourceDiagnostic, SourceResult, bail, warning};
 use typst_library::engine::{Engine, Route, Sink, Traced};
-use typst_library::foundations::{Content, Packed, Resolve, StyleChain};
+use typst_library::foundations::{
+    Content, Context, Func, NativeElement, Packed, Repr, Resolve, Smart, StyleChain,
+    StyledElem, Styles,
+};
 use typst_library::introspection::{
     Introspector, Location, Locator, LocatorLink, SplitLocator, Tag,
 };
 use typst_library::layout::{
-    Abs, ColumnsElem, Dir, Em, Fragment, Frame, PageElem, PlacementScope, Region,
-    Regions, Rel, Size,
+    Abs, Angle, Axes, ColbreakElem, ColspanElem, ColumnCount, ColumnRuleExtent,
+    ColumnsElem, Dir, Em, FillOrder, Fr, Fragment, Frame, FrameItem, PageElem,
+    PlacementScope, Point, Ratio, Region, Regions, Rel, Size, Sizing, SpaceDistribution,
+    VElem, WideChild,
+};
+use typst_library::model::{
+    FootnoteElem, FootnoteEntry, LineNumberingScope, ParElem, ParLine, ParbreakElem,
 };
-use typst_library::model::{FootnoteElem, FootnoteEntry, LineNumberingScope, ParLine};
 use typst_library::routines::{Arenas, FragmentKind, Pair, RealizationKind, Routines};
-use typst_library::text::TextElem;
+use typst_library::text::{SpaceElem, TextElem, TextSize};
+use typst_library::visualize::{
+    Color, ColorSpace, FixedStroke, Geometry, Gradient, LinearGradient, Paint, RelativeTo,
+};
+use typst_syntax::Span;
 use typst_utils::{NonZeroExt, Numeric};
 
 use self::block::{layout_multi_block, layout_single_block};
 use self::collect::{
-    Child, LineChild, MultiChild, MultiSpill, PlacedChild, SingleChild, collect,
+    Child, LineChild, MultiChild, MultiSpill, PinChild, PlacedChild, SingleChild,
+    collect, shrink_to_width,
 };
 use self::compose::{Composer, compose};
 use self::distribute::distribute;
+use self::masonry::layout_masonry;
 
 /// Lays out content into a single region, producing a single frame.
 pub fn layout_frame(
@@ -70,8 +85,7 @@ pub fn layout_fragment(
         locator.track(),
         styles,
         regions,
-        NonZeroUsize::ONE,
-        Rel::zero(),
+        ColumnSettings::SINGLE,
     )
 }
 
@@ -87,22 +101,942 @@ pub fn layout_columns(
     styles: StyleChain,
     regions: Regions,
 ) -> SourceResult<Fragment> {
-    layout_fragment_impl(
-        engine.routines,
-        engine.world,
-        engine.introspector,
-        engine.traced,
-        TrackedMut::reborrow_mut(&mut engine.sink),
-        engine.route.track(),
+    if let Some(styled) = style_after_breaks(elem, engine, styles)? {
+        return layout_columns(&styled, engine, locator, styles, regions);
+    }
+
+    if let Some(parts) = split_at_spans(elem) {
+        let content = Content::sequence(parts);
+        return layout_fragment(engine, &content, locator, styles, regions);
+    }
+
+    let widths = column_widths(elem, styles);
+
+    let schedule = column_schedule(elem, styles);
+    if !schedule.is_empty() && !widths.is_empty() {
+        bail!(
+            elem.span(),
+            "individual column widths cannot be combined with a column count schedule"
+        );
+    }
+
+    if elem
+        .baseline_grid
+        .resolve(styles)
+        .is_some_and(|pitch| pitch <= Abs::zero())
+    {
+        bail!(elem.span(), "baseline grid must be positive");
+    }
+
+    let count = column_count(elem, styles, regions);
+    let gutters = column_gutters(elem, styles);
+    if !gutters.is_empty() && schedule.is_empty() && gutters.len() + 1 != count.get() {
+        bail!(
+            elem.span(),
+            "expected {} gutters for {count} columns, found {}",
+            count.get() - 1,
+            gutters.len();
+            hint: "there is one gutter between each pair of adjacent columns",
+        );
+    }
+
+    // With a schedule, the gutters only apply to regions with a matching
+    // number of columns, but there must be at least one such region.
+    if !gutters.is_empty()
+        && !schedule.is_empty()
+        && !schedule.iter().any(|count| gutters.len() + 1 == count.get())
+    {
+        bail!(
+            elem.span(),
+            "expected a region with {} columns for {} gutters",
+            gutters.len() + 1,
+            gutters.len();
+            hint: "there is one gutter between each pair of adjacent columns",
+        );
+    }
+
+    // One configuration per region with the last one repeating. The regions
+    // after the first one may have a different gutter or number of columns.
+    let gutter = elem.gutter.resolve(styles);
+    let continuation = elem.gutter_continuation.resolve(styles).custom();
+    let configs: Vec<_> = (0..schedule.len().max(1 + continuation.is_some() as usize))
+        .map(|i| {
+            let count = count_in_region(&schedule, count, i);
+            let gutter = if i > 0 { continuation.unwrap_or(gutter) } else { gutter };
+            ColumnConfig::new(styles, regions, count, gutter)
+                .with_gutters(&gutters, regions)
+                .with_widths(&widths, regions)
+        })
+        .collect();
+    let config = &configs[0];
+    if !widths.is_empty() && !regions.size.x.fits(config.total()) {
+        engine.sink.warn(warning!(
+            elem.span(),
+            "column widths and gutters exceed the available width";
+            hint: "use fractional widths to share the remaining space",
+        ));
+    }
+
+    let mut typographic = Styles::new();
+    if elem.typographic_auto.get(styles) {
+        let narrow = config.width < NARROW_COLUMN_WIDTH.resolve(styles);
+        typographic.set(ParElem::justify, !narrow);
+        typographic.set(
+            TextElem::hyphenate,
+            if narrow { Smart::Custom(true) } else { Smart::Auto },
+        );
+    }
+    let styles = styles.chain(&typographic);
+
+    // The overlay's labels must not interfere with the body's locations.
+    let debug = elem.debug.get(styles).then(|| locator.relayout());
+
+    let row = elem.fill_order.get(styles) == FillOrder::Row;
+    let mut fragment = if row || elem.masonry.get(styles) {
+        layout_masonry(elem, engine, locator, styles, regions)?
+    } else if is_passthrough(elem, styles, count) {
+        layout_fragment(engine, &elem.body, locator, styles, regions)?
+    } else {
+        layout_columns_flow(elem, engine, locator, styles, regions)?
+    };
+
+    if let Some(stroke) = elem.rule.resolve(styles) {
+        draw_column_rules(
+            &mut fragment,
+            regions,
+            &configs,
+            stroke.unwrap_or_default(),
+            elem.rule_extend.get(styles),
+            elem.span(),
+        );
+    }
+
+    if let Some(stroke) = elem.edge_rules.resolve(styles) {
+        draw_edge_rules(&mut fragment, regions, stroke.unwrap_or_default(), elem.span());
+    }
+
+    let fills = &elem.fill.get_ref(styles).0;
+    if fills.iter().any(Option::is_some) {
+        draw_column_fills(&mut fragment, &configs, fills, elem.span());
+    }
+
+    if let Some(height) = elem.fade_bottom.resolve(styles)
+        && let Some(frame) = fragment.iter_mut().last()
+    {
+        let fill = match styles.get_cloned(PageElem::fill) {
+            Smart::Custom(Some(Paint::Solid(color))) => color,
+            _ => Color::WHITE,
+        };
+        draw_fade(frame, height, fill, elem.span());
+    }
+
+    if let Some(stroke) = elem.bottom_rule.resolve(styles) {
+        draw_bottom_rule(&mut fragment, stroke.unwrap_or_default(), elem.span());
+    }
+
+    if let Some(locator) = debug {
+        draw_debug_overlay(
+            engine,
+            &mut fragment,
+            locator,
+            styles,
+            &configs,
+            elem.span(),
+        )?;
+    }
+
+    Ok(fragment)
+}
+
+/// Determines the break opportunities in the body of a columns element.
+///
+/// Returns the indices of the in-flow items before which a column break can
+/// occur. The in-flow items are the lines of paragraphs and the blocks, in
+/// order. A break can occur before every item but the first one, except right
+/// after a sticky block and between lines that are kept together to prevent
+/// widows and orphans. Breaks within breakable blocks are not included.
+pub fn column_break_candidates(
+    elem: &Packed<ColumnsElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    regions: Regions,
+) -> SourceResult<Vec<usize>> {
+    let mut locator = locator.split();
+    let mut kind = FragmentKind::Block;
+    let arenas = Arenas::default();
+    let children = (engine.routines.realize)(
+        RealizationKind::LayoutFragment { kind: &mut kind },
+        engine,
+        &mut locator,
+        &arenas,
         &elem.body,
-        locator.track(),
         styles,
-        regions,
-        elem.count.get(styles),
-        elem.gutter.resolve(styles),
-    )
+    )?;
+
+    let count = column_count(elem, styles, regions);
+    let config = ColumnConfig::new(styles, regions, count, elem.gutter.resolve(styles))
+        .with_gutters(&column_gutters(elem, styles), regions)
+        .with_widths(&column_widths(elem, styles), regions);
+    let bump = Bump::new();
+    let children = collect(
+        engine,
+        &bump,
+        &children,
+        locator.next(&()),
+        Size::new(config.width, regions.full),
+        regions.expand.x,
+        count,
+        false,
+        kind.into(),
+    )?;
+
+    let mut candidates = vec![];
+    let mut index = 0;
+    let mut sticky = false;
+    // The space that the lines kept together with the previous line still
+    // need.
+    let mut held = Abs::zero();
+
+    for child in &children {
+        let line = match child {
+            Child::Line(line) => Some(line),
+            Child::Single(_) | Child::Multi(_) => None,
+            Child::Rel(rel, _) => {
+                held -= rel.relative_to(regions.base().y);
+                continue;
+            }
+            _ => continue,
+        };
+
+        let kept = held > Abs::zero() && !held.approx_empty();
+        if index > 0 && !sticky && !kept {
+            candidates.push(index);
+        }
+
+        held = match line {
+            Some(line) => {
+                (held - line.frame.height()).max(line.need - line.frame.height())
+            }
+            None => Abs::zero(),
+        };
+        sticky = match child {
+            Child::Single(single) => single.sticky,
+            Child::Multi(multi) => multi.sticky,
+            _ => false,
+        };
+        index += 1;
+    }
+
+    Ok(candidates)
+}
+
+/// Lays out the columns with the regular flow.
+fn layout_columns_flow(
+    elem: &Packed<ColumnsElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    regions: Regions,
+) -> SourceResult<Fragment> {
+    let mut settings = ColumnSettings {
+        count: column_count(elem, styles, regions),
+        gutter: elem.gutter.resolve(styles),
+        gutter_continuation: elem.gutter_continuation.resolve(styles).custom(),
+        schedule: column_schedule(elem, styles),
+        gutters: column_gutters(elem, styles),
+        widths: column_widths(elem, styles),
+        heights: elem.column_heights.get_cloned(styles).into(),
+        fill: elem.fill_columns.get(styles).custom(),
+        shrink: Ratio::zero(),
+        trim: elem.trim.get(styles),
+        shrink_wrap: elem.shrink_wrap.get(styles),
+        wide_child: elem.wide_child.get(styles),
+        space_distribution: elem.space_distribution.get(styles),
+        prefer_sentence_breaks: elem.prefer_sentence_breaks.get(styles),
+        widow_penalty: elem.widow_penalty.get(styles),
+        orphan_penalty: elem.orphan_penalty.get(styles),
+        colbreak_penalty: elem.colbreak_penalty.get(styles),
+        markers: elem.markers.get(styles),
+        baseline_grid: elem.baseline_grid.resolve(styles),
+        grid_origin: elem.grid_origin.resolve(styles),
+    };
+
+    let layout = |engine: &mut Engine, regions: Regions, settings: ColumnSettings| {
+        layout_fragment_impl(
+            engine.routines,
+            engine.world,
+            engine.introspector,
+            engine.traced,
+            TrackedMut::reborrow_mut(&mut engine.sink),
+            engine.route.track(),
+            &elem.body,
+            locator.track(),
+            styles,
+            regions,
+            settings,
+        )
+    };
+
+    let mut fragment = layout(engine, regions, settings.clone())?;
+
+    // If the content spills into one more region, try to keep it in the
+    // previous region by letting it overfill that one by the slack.
+    let slack = elem.overfill_slack.resolve(styles);
+    let mut overfilled = false;
+    if slack > Abs::zero() && fragment.len() > 1 {
+        let mut backlog = vec![];
+        let index = fragment.len() - 2;
+        if let Some(pod) = enlarge_region(regions, index, slack, &mut backlog) {
+            let enlarged = layout(engine, pod, settings.clone())?;
+            if enlarged.len() < fragment.len() {
+                fragment = enlarged;
+                overfilled = true;
+            }
+        }
+    }
+
+    // If the content spills into one more region than necessary, try to absorb
+    // it by shrinking the spacing. If that doesn't save a region, we fall back
+    // to the normal layout.
+    if elem.avoid_single_region.get(styles) && fragment.len() > 1 {
+        let shrunk_settings =
+            ColumnSettings { shrink: SHRINK_TOLERANCE, ..settings.clone() };
+        let shrunk = layout(engine, regions, shrunk_settings.clone())?;
+        if shrunk.len() < fragment.len() {
+            fragment = shrunk;
+            settings = shrunk_settings;
+            overfilled = false;
+        }
+    }
+
+    // If the columns in the last region are shorter than the minimum height,
+    // pull content over by searching for the largest height of the previous
+    // region at which the last region reaches the minimum height. Balancing
+    // takes precedence as it evens out the last region on its own.
+    let min_height = elem.min_height.resolve(styles).relative_to(regions.base().y);
+    if min_height > Abs::zero()
+        && !elem.balance.get(styles)
+        && !regions.expand.y
+        && !overfilled
+        && fragment.len() > 1
+        && fragment
+            .as_slice()
+            .last()
+            .is_some_and(|last| last.height() < min_height)
+    {
+        let index = fragment.len() - 2;
+        let original = regions.iter().nth(index).map_or(Abs::zero(), |size| size.y);
+        let mut hi = original;
+        let mut lo = (original - min_height).max(Abs::zero());
+        let mut pulled = None;
+        while (hi - lo).to_pt() > BALANCE_PRECISION {
+            let mid = (lo + hi) / 2.0;
+            let mut backlog = vec![];
+            let Some(pod) = limit_region(regions, index, mid, &mut backlog) else {
+                break;
+            };
+            let attempt = layout(engine, pod, settings.clone())?;
+            let end = attempt.as_slice().last().map_or(Abs::zero(), Frame::height);
+            if attempt.len() != fragment.len() || !original.fits(end) {
+                // Too much content was pulled over.
+                lo = mid;
+            } else if end < min_height {
+                hi = mid;
+            } else {
+                lo = mid;
+                pulled = Some(attempt);
+            }
+        }
+
+        if let Some(pulled) = pulled {
+            fragment = pulled;
+        }
+    }
+
+    // Balance the columns in the last region by searching for the smallest
+    // height of that region at which the content still fits into it. An
+    // overfilled region is left as is.
+    if elem.balance.get(styles)
+        && settings.count_of(fragment.len().saturating_sub(1)).get() > 1
+        && !regions.expand.y
+        && !overfilled
+        && let Some(last) = fragment.as_slice().last()
+    {
+        let index = fragment.len() - 1;
+        let mut hi = last.height();
+        let mut lo = hi / settings.count_of(index).get() as f64;
+        let mut balanced = None;
+
+        // The even split is tried first since it often fits exactly, e.g. for
+        // blocks of equal height.
+        let mut mid = lo;
+        loop {
+            let mut backlog = vec![];
+            let Some(pod) = limit_region(regions, index, mid, &mut backlog) else {
+                break;
+            };
+            let attempt = layout(engine, pod, settings.clone())?;
+            if attempt.len() == fragment.len() {
+                hi = mid;
+                balanced = Some(attempt);
+            } else {
+                lo = mid;
+            }
+            if (hi - lo).to_pt() <= BALANCE_PRECISION {
+                break;
+            }
+            mid = (lo + hi) / 2.0;
+        }
+
+        if let Some(balanced) = balanced {
+            fragment = balanced;
+        }
+    }
+
+    // Align the bottoms of the columns by laying them out again into regions
+    // as tall as the tallest column of each region, distributing the extra
+    // space between the blocks. The columns break at the same points as
+    // before since the regions are at most as tall as before.
+    if elem.bottom_align.get(styles) && settings.count.get() > 1 && !regions.expand.y {
+        let heights: Vec<Abs> = fragment.iter().map(Frame::height).collect();
+        let pod = Regions {
+            size: Size::new(regions.size.x, heights[0]),
+            expand: Axes::new(regions.expand.x, true),
+            full: regions.full,
+            backlog: &heights[1..],
+            last: None,
+        };
+        let aligned = layout(
+            engine,
+            pod,
+            ColumnSettings {
+                space_distribution: SpaceDistribution::Between,
+                ..settings
+            },
+        )?;
+        if aligned.len() == fragment.len() {
+            fragment = aligned;
+        }
+    }
+
+    Ok(fragment)
+}
+
+/// Properties of columns that don't affect how a single column lays out its
+/// body. They only concern multiple columns, are handled before the flow is
+/// laid out, or are drawn on top of the laid out body.
+const PASSTHROUGH_PROPERTIES: &[&str] = &[
+    "count",
+    "gutter",
+    "gutters",
+    "gutter-continuation",
+    "masonry",
+    "fill-order",
+    "balance",
+    "bottom-align",
+    "typographic-auto",
+    "fill",
+    "rule",
+    "rule-extend",
+    "fade-bottom",
+    "debug",
+    "edge-rules",
+    "bottom-rule",
+    "gap-before",
+    "gap-after",
+    "grid-origin",
+];
+
+/// Whether the columns can be laid out like their body without any columns.
+///
+/// This is the case for a single column whose other properties are all at
+/// their defaults, except for those in [`PASSTHROUGH_PROPERTIES`]. Checking
+/// for defaults instead of listing the properties that matter means that a
+/// new property opts out of the passthrough until it is added to the list.
+fn is_passthrough(
+    elem: &Packed<ColumnsElem>,
+    styles: StyleChain,
+    count: NonZeroUsize,
+) -> bool {
+    if count.get() != 1
+        || column_schedule(elem, styles).iter().any(|count| count.get() != 1)
+    {
+        return false;
+    }
+
+    let element = ColumnsElem::ELEM;
+    element
+        .params()
+        .iter()
+        .filter(|param| param.settable && !PASSTHROUGH_PROPERTIES.contains(&param.name))
+        .all(|param| {
+            element.field_id(param.name).is_some_and(|id| {
+                elem.pack_ref().get(id, Some(styles)).ok()
+                    == element.field_from_styles(id, StyleChain::default()).ok()
+            })
+        })
+}
+
+/// Enlarges the region with the given index by the given amount. The modified
+/// backlog is stored in `backlog`.
+///
+/// Returns `None` if there is no such region.
+fn enlarge_region<'b>(
+    regions: Regions,
+    index: usize,
+    amount: Abs,
+    backlog: &'b mut Vec<Abs>,
+) -> Option<Regions<'b>> {
+    let mut size = regions.size;
+    backlog.extend_from_slice(regions.backlog);
+    if index == 0 {
+        size.y += amount;
+    } else {
+        while backlog.len() < index {
+            backlog.push(regions.last?);
+        }
+        backlog[index - 1] += amount;
+    }
+
+    Some(Regions {
+        size,
+        expand: regions.expand,
+        full: regions.full,
+        backlog,
+        last: regions.last,
+    })
+}
+
+/// Limits the region with the given index to the given height and drops the
+/// regions after it, except for one with the region's original height that
+/// content can spill into if it doesn't fit.
+fn limit_region<'b>(
+    regions: Regions,
+    index: usize,
+    height: Abs,
+    backlog: &'b mut Vec<Abs>,
+) -> Option<Regions<'b>> {
+    let original = regions.iter().nth(index)?.y;
+    let mut size = regions.size;
+    backlog.extend(regions.iter().skip(1).take(index).map(|region| region.y));
+    if index == 0 {
+        size.y = height;
+    } else {
+        backlog[index - 1] = height;
+    }
+    backlog.push(original);
+
+    Some(Regions {
+        size,
+        expand: regions.expand,
+        full: regions.full,
+        backlog,
+        last: None,
+    })
+}
+
+/// The precision in points up to which the height of balanced columns is
+/// searched for.
+const BALANCE_PRECISION: f64 = 1.0;
+
+/// The column width below which `typographic-auto` sets text ragged instead of
+/// justified.
+const NARROW_COLUMN_WIDTH: Em = Em::new(20.0);
+
+/// The number of characters per line that an automatic column count aims for.
+const TARGET_LINE_CHARS: f64 = 65.0;
+
+/// The estimated average width of a character, used for the automatic column
+/// count.
+const AVERAGE_CHAR_WIDTH: Em = Em::new(0.5);
+
+/// The maximum number of columns an automatic column count yields.
+const MAX_AUTO_COLUMNS: usize = 6;
+
+/// Determines the number of columns, resolving an automatic count based on
+/// the region width and the text size, and a minimum column width based on
+/// the region width.
+pub(super) fn column_count(
+    elem: &Packed<ColumnsElem>,
+    styles: StyleChain,
+    regions: Regions,
+) -> NonZeroUsize {
+    if let Some(count) = NonZeroUsize::new(elem.widths.get_ref(styles).len()) {
+        return count;
+    }
+
+    let (target, max) = match elem.count.get_ref(styles) {
+        Smart::Custom(ColumnCount::Fixed(count)) => return *count,
+        Smart::Custom(count @ ColumnCount::PerItem { .. }) => {
+            return count.for_items(count_items(&elem.body)).unwrap();
+        }
+        Smart::Custom(ColumnCount::Schedule(counts)) => return counts[0],
+        Smart::Custom(ColumnCount::MinWidth(width)) => {
+            (width.resolve(styles), usize::MAX)
+        }
+        Smart::Auto => {
+            (AVERAGE_CHAR_WIDTH.resolve(styles) * TARGET_LINE_CHARS, MAX_AUTO_COLUMNS)
+        }
+    };
+
+    let gutter = elem.gutter.resolve(styles).relative_to(regions.base().x);
+    if !regions.size.x.is_finite() || target + gutter <= Abs::zero() {
+        return NonZeroUsize::ONE;
+    }
+
+    let fitting = ((regions.size.x + gutter) / (target + gutter)).floor();
+    let count = (fitting.max(0.0) as usize).clamp(1, max);
+    NonZeroUsize::new(count).unwrap()
+}
+
+/// The column counts of successive regions if the count is a schedule.
+fn column_schedule(
+    elem: &Packed<ColumnsElem>,
+    styles: StyleChain,
+) -> EcoVec<NonZeroUsize> {
+    match elem.count.get_ref(styles) {
+        Smart::Custom(ColumnCount::Schedule(counts)) => counts.iter().copied().collect(),
+        _ => EcoVec::new(),
+    }
+}
+
+/// The number of columns in the region with the given index according to the
+/// schedule. Without a schedule, all regions have `count` columns.
+fn count_in_region(
+    schedule: &[NonZeroUsize],
+    count: NonZeroUsize,
+    index: usize,
+) -> NonZeroUsize {
+    schedule.get(index).or(schedule.last()).copied().unwrap_or(count)
 }
 
+/// Applies the styles of column breaks among the top-level children of the
+/// columns' body to the content after them, up to the next column break or
+/// the end of the body.
+///
+/// Returns `None` if no column break has a style.
+fn style_after_breaks(
+    elem: &Packed<ColumnsElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+) -> SourceResult<Option<Packed<ColumnsElem>>> {
+    let mut children = vec![];
+    elem.body
+        .sequence_recursive_for_each(&mut |child| children.push(child.clone()));
+    let styled = |child: &Content| {
+        child
+            .to_packed::<ColbreakElem>()
+            .is_some_and(|colbreak| colbreak.style.get_ref(styles).is_some())
+    };
+    if !children.iter().any(styled) {
+        return Ok(None);
+    }
+
+    let context = Context::new(None, Some(styles));
+    let mut output = vec![];
+    let mut pending: Option<(Func, Vec<Content>)> = None;
+    let mut finish = |output: &mut Vec<Content>,
+                      pending: Option<(Func, Vec<Content>)>| {
+        if let Some((func, following)) = pending {
+            let body = Content::sequence(following);
+            output.push(func.call(engine, context.track(), [body])?.display());
+        }
+        SourceResult::Ok(())
+    };
+
+    for child in children {
+        if let Some(colbreak) = child.to_packed::<ColbreakElem>() {
+            finish(&mut output, pending.take())?;
+            pending = colbreak.style.get_cloned(styles).map(|func| (func, vec![]));
+
+            // The style is applied now, so it must not be applied again.
+            let mut colbreak = colbreak.clone();
+            colbreak.style.set(None);
+            output.push(colbreak.pack());
+        } else if let Some((_, following)) = &mut pending {
+            following.push(child);
+        } else {
+            output.push(child);
+        }
+    }
+    finish(&mut output, pending)?;
+
+    let mut columns = ColumnsElem::clone(elem);
+    columns.body = Content::sequence(output);
+    Ok(Some(Packed::new(columns).spanned(elem.span())))
+}
+
+/// Splits the body of a columns element at the column spans among its
+/// top-level children into separate columns elements with the spans in
+/// between. The parts before a span are balanced.
+///
+/// Returns `None` if there are no spans.
+fn split_at_spans(elem: &Packed<ColumnsElem>) -> Option<Vec<Content>> {
+    let mut segments = vec![vec![]];
+    let mut spans = vec![];
+    collect_spans(&elem.body, &mut segments, &mut spans);
+    if spans.is_empty() {
+        return None;
+    }
+
+    let mut parts = vec![];
+    let last = segments.len() - 1;
+    for (i, segment) in segments.into_iter().enumerate() {
+        let body = Content::sequence(segment);
+        if count_items(&body) > 0 {
+            let mut columns = ColumnsElem::clone(elem);
+            columns.body = body;
+            if i < last {
+                columns.balance.set(true);
+            }
+            parts.push(Packed::new(columns).spanned(elem.span()).pack());
+        }
+        if let Some(span) = spans.get(i) {
+            parts.push(span.clone());
+        }
+    }
+    Some(parts)
+}
+
+/// Distributes the top-level children of content into the segments between
+/// the column spans. Spans within styled content keep their styles.
+fn collect_spans(
+    content: &Content,
+    segments: &mut Vec<Vec<Content>>,
+    spans: &mut Vec<Content>,
+) {
+    content.sequence_recursive_for_each(&mut |child| {
+        if child.is::<ColspanElem>() {
+            spans.push(child.clone());
+            segments.push(vec![]);
+        } else if let Some(styled) = child.to_packed::<StyledElem>() {
+            let mut inner_segments = vec![vec![]];
+            let mut inner_spans = vec![];
+            collect_spans(&styled.child, &mut inner_segments, &mut inner_spans);
+            if inner_spans.is_empty() {
+                segments.last_mut().unwrap().push(child.clone());
+                return;
+            }
+
+            let styles = &styled.styles;
+            for (i, segment) in inner_segments.into_iter().enumerate() {
+                if i > 0 {
+                    spans
+                        .push(inner_spans[i - 1].clone().styled_with_map(styles.clone()));
+                    segments.push(vec![]);
+                }
+                let segment = Content::sequence(segment).styled_with_map(styles.clone());
+                segments.last_mut().unwrap().push(segment);
+            }
+        } else {
+            segments.last_mut().unwrap().push(child.clone());
+        }
+    });
+}
+
+/// Counts the top-level items of the body of a columns element, skipping
+/// spaces, paragraph breaks, and spacing.
+fn count_items(body: &Content) -> usize {
+    let mut count = 0;
+    body.sequence_recursive_for_each(&mut |child| {
+        if let Some(styled) = child.to_packed::<StyledElem>() {
+            count += count_items(&styled.child);
+        } else if !child.is::<SpaceElem>()
+            && !child.is::<ParbreakElem>()
+            && !child.is::<VElem>()
+        {
+            count += 1;
+        }
+    });
+    count
+}
+
+/// Resolves the widths of the individual columns.
+fn column_widths(elem: &Packed<ColumnsElem>, styles: StyleChain) -> EcoVec<ColumnWidth> {
+    elem.widths
+        .get_ref(styles)
+        .iter()
+        .map(|width| match width {
+            Sizing::Auto => ColumnWidth::Fr(Fr::one()),
+            Sizing::Rel(rel) => ColumnWidth::Rel(rel.resolve(styles)),
+            Sizing::Fr(fr) => ColumnWidth::Fr(*fr),
+        })
+        .collect()
+}
+
+/// Resolves the gaps between the individual columns.
+fn column_gutters(
+    elem: &Packed<ColumnsElem>,
+    styles: StyleChain,
+) -> EcoVec<Smart<Rel<Abs>>> {
+    elem.gutters
+        .get_ref(styles)
+        .iter()
+        .map(|gutter| gutter.resolve(styles))
+        .collect()
+}
+
+/// Draws vertical rules in the gutters between the columns in each region.
+fn draw_column_rules(
+    fragment: &mut Fragment,
+    mut regions: Regions,
+    configs: &[ColumnConfig],
+    stroke: FixedStroke,
+    extent: ColumnRuleExtent,
+    span: Span,
+) {
+    for (i, frame) in fragment.iter_mut().enumerate() {
+        let config = &configs[i.min(configs.len() - 1)];
+        if extent == ColumnRuleExtent::Region && regions.size.y.is_finite() {
+            frame.size_mut().y = frame.height().max(regions.size.y);
+        }
+
+        let line = Geometry::Line(Point::with_y(frame.height())).stroked(stroke.clone());
+        for i in 1..config.count {
+            // The rule sits in the middle of the gutter before the i-th column.
+            let start = config.offset(i, frame.width());
+            let gutter = config.gutter_after(i - 1);
+            let x = if config.dir == Dir::LTR {
+                start - gutter / 2.0
+            } else {
+                start + config.width_of(i) + gutter / 2.0
+            };
+            frame.push(Point::with_x(x), FrameItem::Shape(line.clone(), span));
+        }
+        regions.next();
+    }
+}
+
+/// Fills the background of the columns in each region.
+///
+/// The rectangles are prepended so that they end up below the content.
+fn draw_column_fills(
+    fragment: &mut Fragment,
+    configs: &[ColumnConfig],
+    fills: &[Option<Paint>],
+    span: Span,
+) {
+    for (i, frame) in fragment.iter_mut().enumerate() {
+        let config = &configs[i.min(configs.len() - 1)];
+        let (width, height) = (frame.width(), frame.height());
+        frame.prepend_multiple((0..config.count).filter_map(|c| {
+            let fill = fills[c % fills.len()].clone()?;
+            let rect = Geometry::Rect(Size::new(config.width_of(c), height));
+            let pos = Point::with_x(config.offset(c, width));
+            Some((pos, FrameItem::Shape(rect.filled(fill), span)))
+        }));
+    }
+}
+
+/// Draws a gradient from transparent to the given fill over the bottom of the
+/// frame.
+fn draw_fade(frame: &mut Frame, height: Abs, fill: Color, span: Span) {
+    // CMYK colors don't support transparency.
+    let fill = match fill.space() {
+        ColorSpace::Cmyk => fill.to_space(ColorSpace::Srgb),
+        _ => fill,
+    };
+    let height = height.min(frame.height());
+    let gradient = Gradient::Linear(Arc::new(LinearGradient {
+        stops: vec![(fill.with_alpha(0.0), Ratio::zero()), (fill, Ratio::one())],
+        angle: Angle::deg(90.0),
+        space: ColorSpace::Oklab,
+        relative: Smart::Custom(RelativeTo::Self_),
+        anti_alias: true,
+    }));
+    let rect = Geometry::Rect(Size::new(frame.width(), height)).filled(gradient);
+    let pos = Point::with_y(frame.height() - height);
+    frame.push(pos, FrameItem::Shape(rect, span));
+}
+
+/// Overlays translucent rectangles for the columns and gutters in each region,
+/// labelled with their widths.
+///
+/// The overlay is pushed on top of the existing frames and doesn't change
+/// their size.
+fn draw_debug_overlay(
+    engine: &mut Engine,
+    fragment: &mut Fragment,
+    locator: Locator,
+    styles: StyleChain,
+    configs: &[ColumnConfig],
+    span: Span,
+) -> SourceResult<()> {
+    let mut locator = locator.split();
+    let small = TextElem::size.set(TextSize(Abs::pt(6.0).into())).wrap();
+    let styles = styles.chain(&small);
+    let pod = Region::new(Size::splat(Abs::inf()), Axes::splat(false));
+
+    for (i, frame) in fragment.iter_mut().enumerate() {
+        let config = &configs[i.min(configs.len() - 1)];
+        let (width, height) = (frame.width(), frame.height());
+
+        let mut boxes = vec![];
+        for c in 0..config.count {
+            let x = config.offset(c, width);
+            boxes.push((x, config.width_of(c), Color::BLUE.with_alpha(0.15)));
+            if c > 0 {
+                // The gutter starts at the end of the left one of the two
+                // columns.
+                let prev = config.offset(c - 1, width);
+                let x = if x < prev {
+                    x + config.width_of(c)
+                } else {
+                    prev + config.width_of(c - 1)
+                };
+                boxes.push((x, config.gutter_after(c - 1), Color::RED.with_alpha(0.15)));
+            }
+        }
+
+        for (x, w, color) in boxes {
+            let rect = Geometry::Rect(Size::new(w, height)).filled(color);
+            frame.push(Point::with_x(x), FrameItem::Shape(rect, span));
+
+            let label = TextElem::packed(w.repr()).spanned(span);
+            let label = layout_frame(engine, &label, locator.next(&()), styles, pod)?;
+            frame.push_frame(Point::with_x(x), label);
+        }
+    }
+
+    Ok(())
+}
+
+/// Draws horizontal rules at the top and bottom of the columns in each region.
+fn draw_edge_rules(
+    fragment: &mut Fragment,
+    mut regions: Regions,
+    stroke: FixedStroke,
+    span: Span,
+) {
+    let count = fragment.len();
+    for (i, frame) in fragment.iter_mut().enumerate() {
+        // Fill up all but the last region so that the bottom rules end up at
+        // the same position in each region.
+        if i + 1 < count && regions.size.y.is_finite() {
+            frame.size_mut().y = frame.height().max(regions.size.y);
+        }
+
+        let line = Geometry::Line(Point::with_x(frame.width())).stroked(stroke.clone());
+        frame.push(Point::zero(), FrameItem::Shape(line.clone(), span));
+        frame.push(Point::with_y(frame.height()), FrameItem::Shape(line, span));
+        regions.next();
+    }
+}
+
+/// Draws a horizontal rule across all columns and gutters at the bottom of
+/// each region.
+fn draw_bottom_rule(fragment: &mut Fragment, stroke: FixedStroke, span: Span) {
+    for frame in fragment.iter_mut() {
+        let line = Geometry::Line(Point::with_x(frame.width())).stroked(stroke.clone());
+        frame.push(Point::with_y(frame.height()), FrameItem::Shape(line, span));
+    }
+}
+
+/// How much flexible spacing may at most be shrunk to avoid a single trailing
+/// region. See [`ColumnsElem::avoid_single_region`].
+const SHRINK_TOLERANCE: Ratio = Ratio::new(0.2);
+
 /// The cached, internal implementation of [`layout_fragment`].
 #[comemo::memoize]
 #[allow(clippy::too_many_arguments)]
@@ -117,8 +1051,7 @@ fn layout_fragment_impl(
     locator: Tracked<Locator>,
     styles: StyleChain,
     regions: Regions,
-    columns: NonZeroUsize,
-    column_gutter: Rel<Abs>,
+    columns: ColumnSettings,
 ) -> SourceResult<Fragment> {
     if !regions.size.x.is_finite() && regions.expand.x {
         bail!(content.span(), "cannot expand into infinite width");
@@ -158,11 +1091,116 @@ fn layout_fragment_impl(
         styles,
         regions,
         columns,
-        column_gutter,
         kind.into(),
     )
 }
 
+/// Settings for the columns of a flow.
+#[derive(Debug, Clone, Hash)]
+pub struct ColumnSettings {
+    /// The number of columns.
+    pub count: NonZeroUsize,
+    /// The gutter between the columns.
+    pub gutter: Rel<Abs>,
+    /// The gutter between the columns in all regions but the first one, if
+    /// it differs from `gutter`.
+    pub gutter_continuation: Option<Rel<Abs>>,
+    /// The number of columns in successive regions, with the last entry
+    /// repeating. Empty if all regions have `count` columns.
+    pub schedule: EcoVec<NonZeroUsize>,
+    /// The gaps between the individual columns. Empty if they are all
+    /// `gutter` wide.
+    pub gutters: EcoVec<Smart<Rel<Abs>>>,
+    /// The widths of the individual columns. Empty if they are equally wide.
+    pub widths: EcoVec<ColumnWidth>,
+    /// The available height of each column relative to the region's height.
+    /// Columns without an entry take up the full height.
+    pub heights: EcoVec<Ratio>,
+    /// How many columns receive content in each region. All of them if
+    /// `None`.
+    pub fill: Option<NonZeroUsize>,
+    /// By how much to shrink automatic block, paragraph, and line spacing.
+    pub shrink: Ratio,
+    /// Whether to drop empty lines at column boundaries.
+    pub trim: bool,
+    /// Whether columns shrink to the width of their content.
+    pub shrink_wrap: bool,
+    /// How to handle children that are wider than all columns combined.
+    pub wide_child: WideChild,
+    /// Where to put extra vertical space in a column.
+    pub space_distribution: SpaceDistribution,
+    /// Whether to prefer column breaks at the end of sentences.
+    pub prefer_sentence_breaks: bool,
+    /// The penalty for a column break that leaves a widow.
+    pub widow_penalty: i64,
+    /// The penalty for a column break that leaves an orphan.
+    pub orphan_penalty: i64,
+    /// The penalty for a column break within a paragraph.
+    pub colbreak_penalty: i64,
+    /// Whether to emit a marker for each column.
+    pub markers: bool,
+    /// The distance between the lines of the baseline grid that the first
+    /// line of each column snaps to, if any.
+    pub baseline_grid: Option<Abs>,
+    /// The offset of a line of the baseline grid from the top of the columns.
+    pub grid_origin: Abs,
+}
+
+impl ColumnSettings {
+    /// Settings for a flow without columns.
+    pub const SINGLE: Self = Self {
+        count: NonZeroUsize::ONE,
+        gutter: Rel { rel: Ratio::zero(), abs: Abs::zero() },
+        gutter_continuation: None,
+        schedule: EcoVec::new(),
+        gutters: EcoVec::new(),
+        widths: EcoVec::new(),
+        heights: EcoVec::new(),
+        fill: None,
+        shrink: Ratio::zero(),
+        trim: false,
+        shrink_wrap: false,
+        wide_child: WideChild::Overflow,
+        space_distribution: SpaceDistribution::Bottom,
+        prefer_sentence_breaks: false,
+        widow_penalty: 0,
+        orphan_penalty: 0,
+        colbreak_penalty: 0,
+        markers: false,
+        baseline_grid: None,
+        grid_origin: Abs::zero(),
+    };
+
+    /// The number of columns in the region with the given index.
+    fn count_of(&self, index: usize) -> NonZeroUsize {
+        count_in_region(&self.schedule, self.count, index)
+    }
+
+    /// The column configuration for the region with the given index.
+    fn config_for(
+        &self,
+        shared: StyleChain,
+        regions: Regions,
+        index: usize,
+    ) -> ColumnConfig {
+        let gutter = match self.gutter_continuation {
+            Some(gutter) if index > 0 => gutter,
+            _ => self.gutter,
+        };
+        ColumnConfig::new(shared, regions, self.count_of(index), gutter)
+            .with_settings(self, regions)
+    }
+}
+
+/// The width of an individual column.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub enum ColumnWidth {
+    /// A fixed width or one relative to the available width.
+    Rel(Rel<Abs>),
+    /// A share of the space left by the other columns and the gutters.
+    Fr(Fr),
+}
+
 /// The mode a flow can be laid out in.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum FlowMode {
@@ -192,23 +1230,30 @@ pub fn layout_flow<'a>(
     locator: &mut SplitLocator<'a>,
     shared: StyleChain<'a>,
     mut regions: Regions,
-    columns: NonZeroUsize,
-    column_gutter: Rel<Abs>,
+    columns: ColumnSettings,
     mode: FlowMode,
 ) -> SourceResult<Fragment> {
     // Prepare configuration that is shared across the whole flow.
-    let config = configuration(shared, regions, columns, column_gutter, mode);
+    let mut config = configuration(shared, regions, &columns, mode);
+
+    // Paragraphs must fit into the columns of every region.
+    let narrowest = (1..columns.schedule.len())
+        .map(|i| columns.config_for(shared, regions, i).width)
+        .fold(config.columns.width, Abs::min);
 
     // Collect the elements into pre-processed children. These are much easier
     // to handle than the raw elements.
+    let most = columns.schedule.iter().chain([&columns.count]).max().copied();
     let bump = Bump::new();
     let children = collect(
         engine,
         &bump,
         children,
         locator.next(&()),
-        Size::new(config.columns.width, regions.full),
+        Size::new(narrowest, regions.full),
         regions.expand.x,
+        most.unwrap_or(columns.count),
+        config.columns.count > 1 && config.wide_child == WideChild::Span,
         mode,
     )?;
 
@@ -227,6 +1272,21 @@ pub fn layout_flow<'a>(
         }
 
         regions.next();
+
+        // Regions after the first one may have a different gutter or number
+        // of columns and thus differently wide columns.
+        let index = finished.len();
+        if (index == 1 && columns.gutter_continuation.is_some())
+            || index < columns.schedule.len()
+        {
+            config.columns = columns.config_for(shared, regions, index);
+
+            // A targeted column break may aim at a column that this region
+            // lacks, so it continues in the last filled one instead.
+            if let Some(column) = &mut work.column {
+                *column = (*column).min(config.columns.fill.saturating_sub(1));
+            }
+        }
     }
 
     Ok(Fragment::frames(finished))
@@ -236,24 +1296,25 @@ pub fn layout_flow<'a>(
 fn configuration<'x>(
     shared: StyleChain<'x>,
     regions: Regions,
-    columns: NonZeroUsize,
-    column_gutter: Rel<Abs>,
+    columns: &ColumnSettings,
     mode: FlowMode,
 ) -> Config<'x> {
     Config {
         mode,
         shared,
-        columns: {
-            let mut count = columns.get();
-            if !regions.size.x.is_finite() {
-                count = 1;
-            }
-
-            let gutter = column_gutter.relative_to(regions.base().x);
-            let width = (regions.size.x - gutter * (count - 1) as f64) / count as f64;
-            let dir = shared.resolve(TextElem::dir);
-            ColumnConfig { count, width, gutter, dir }
-        },
+        columns: columns.config_for(shared, regions, 0),
+        shrink: columns.shrink,
+        trim: columns.trim,
+        shrink_wrap: columns.shrink_wrap,
+        wide_child: columns.wide_child,
+        space_distribution: columns.space_distribution,
+        prefer_sentence_breaks: columns.prefer_sentence_breaks,
+        widow_penalty: columns.widow_penalty,
+        orphan_penalty: columns.orphan_penalty,
+        colbreak_penalty: columns.colbreak_penalty,
+        markers: columns.markers,
+        baseline_grid: columns.baseline_grid.filter(|pitch| *pitch > Abs::zero()),
+        grid_origin: columns.grid_origin,
         footnote: FootnoteConfig {
             separator: shared.get_cloned(FootnoteEntry::separator),
             clearance: shared.resolve(FootnoteEntry::clearance),
@@ -300,6 +1361,12 @@ struct Work<'a, 'b> {
     footnote_spill: Option<std::vec::IntoIter<Frame>>,
     /// Queued tags that will be attached to the next frame.
     tags: EcoVec<&'a Tag>,
+    /// Content pinned to the top of a column that is still to be placed. All
+    /// of it is placed in the first region.
+    pins: EcoVec<&'b PinChild>,
+    /// The zero-based index of the column that a targeted column break
+    /// continues in. Other columns are left empty until it is reached.
+    column: Option<usize>,
     /// Identifies floats and footnotes that can be skipped if visited because
     /// they were already handled and incorporated as column or page level
     /// insertions.
@@ -316,6 +1383,14 @@ impl<'a, 'b> Work<'a, 'b> {
             footnotes: EcoVec::new(),
             footnote_spill: None,
             tags: EcoVec::new(),
+            pins: children
+                .iter()
+                .filter_map(|child| match child {
+                    Child::Pin(pin) => Some(&**pin),
+                    _ => None,
+                })
+                .collect(),
+            column: None,
             skips: Rc::new(FxHashSet::default()),
         }
     }
@@ -358,6 +1433,32 @@ struct Config<'x> {
     shared: StyleChain<'x>,
     /// Settings for columns.
     columns: ColumnConfig,
+    /// By how much automatic spacing between blocks, paragraphs, and lines is
+    /// shrunk. Zero unless the flow tries to avoid a single trailing region.
+    shrink: Ratio,
+    /// Whether to drop empty lines at the start and end of a column.
+    trim: bool,
+    /// Whether columns shrink to the width of their content.
+    shrink_wrap: bool,
+    /// How to handle children that are wider than all columns combined.
+    wide_child: WideChild,
+    /// Where to put extra vertical space in a column.
+    space_distribution: SpaceDistribution,
+    /// Whether to prefer column breaks at the end of sentences.
+    prefer_sentence_breaks: bool,
+    /// The penalty for a column break that leaves a widow.
+    widow_penalty: i64,
+    /// The penalty for a column break that leaves an orphan.
+    orphan_penalty: i64,
+    /// The penalty for a column break within a paragraph.
+    colbreak_penalty: i64,
+    /// Whether to emit a marker for each column.
+    markers: bool,
+    /// The distance between the lines of the baseline grid that the first
+    /// line of each column snaps to, if any. Always positive.
+    baseline_grid: Option<Abs>,
+    /// The offset of a line of the baseline grid from the top of a column.
+    grid_origin: Abs,
     /// Settings for footnotes.
     footnote: FootnoteConfig,
     /// Settings for line numbers.
@@ -380,13 +1481,156 @@ struct FootnoteConfig {
 struct ColumnConfig {
     /// The number of columns.
     count: usize,
-    /// The width of each column.
+    /// The width of each column. If the columns have different widths, the
+    /// width of the narrowest one, for which paragraphs are broken into
+    /// lines.
     width: Abs,
+    /// The widths of the individual columns if they differ. Empty if all
+    /// columns are `width` wide.
+    widths: EcoVec<Abs>,
     /// The amount of space between columns.
     gutter: Abs,
+    /// The gaps between the individual columns if they differ. Empty if all
+    /// gaps are `gutter` wide.
+    gutters: EcoVec<Abs>,
     /// The horizontal direction in which columns progress. Defined by
     /// `text.dir`.
     dir: Dir,
+    /// The available height of each column relative to the region's height.
+    heights: EcoVec<Ratio>,
+    /// How many columns receive content in each region. The remaining ones
+    /// stay empty.
+    fill: usize,
+}
+
+impl ColumnConfig {
+    /// Determine the column configuration for the given regions.
+    fn new(
+        shared: StyleChain,
+        regions: Regions,
+        columns: NonZeroUsize,
+        column_gutter: Rel<Abs>,
+    ) -> Self {
+        let mut count = columns.get();
+        if !regions.size.x.is_finite() {
+            count = 1;
+        }
+
+        let gutter = column_gutter.relative_to(regions.base().x);
+        let width = (regions.size.x - gutter * (count - 1) as f64) / count as f64;
+        let dir = shared.resolve(TextElem::dir);
+        Self {
+            count,
+            width,
+            widths: EcoVec::new(),
+            gutter,
+            gutters: EcoVec::new(),
+            dir,
+            heights: EcoVec::new(),
+            fill: count,
+        }
+    }
+
+    /// Applies the gutters, column widths and heights, and the fill cap from
+    /// the settings.
+    fn with_settings(mut self, settings: &ColumnSettings, regions: Regions) -> Self {
+        self = self
+            .with_gutters(&settings.gutters, regions)
+            .with_widths(&settings.widths, regions);
+        self.heights = settings.heights.clone();
+        self.fill = settings.fill.map_or(self.count, |fill| fill.get().min(self.count));
+        self
+    }
+
+    /// Applies individual gaps between the columns. `auto` entries keep the
+    /// default gutter. The columns share the remaining space equally.
+    fn with_gutters(mut self, gutters: &[Smart<Rel<Abs>>], regions: Regions) -> Self {
+        if gutters.len() + 1 != self.count {
+            return self;
+        }
+
+        let base = regions.base().x;
+        let default = self.gutter;
+        self.gutters = gutters
+            .iter()
+            .map(|gutter| match gutter {
+                Smart::Auto => default,
+                Smart::Custom(rel) => rel.relative_to(base),
+            })
+            .collect();
+        self.width = (regions.size.x - self.gutter_total()) / self.count as f64;
+        self
+    }
+
+    /// Applies individual column widths. Fixed widths are subtracted from the
+    /// available space and the rest is distributed between the fractional
+    /// columns.
+    fn with_widths(mut self, widths: &[ColumnWidth], regions: Regions) -> Self {
+        if widths.len() != self.count || self.count == 1 {
+            return self;
+        }
+
+        let base = regions.base().x;
+        let mut fixed = Abs::zero();
+        let mut fr = Fr::zero();
+        for width in widths {
+            match width {
+                ColumnWidth::Rel(rel) => fixed += rel.relative_to(base),
+                ColumnWidth::Fr(v) => fr += *v,
+            }
+        }
+
+        let remaining = (regions.size.x - self.gutter_total() - fixed).max(Abs::zero());
+        self.widths = widths
+            .iter()
+            .map(|width| match width {
+                ColumnWidth::Rel(rel) => rel.relative_to(base),
+                ColumnWidth::Fr(v) => v.share(fr, remaining),
+            })
+            .collect();
+        self.width = self.widths.iter().copied().fold(Abs::inf(), Abs::min);
+        self
+    }
+
+    /// The width of the column with the given index.
+    fn width_of(&self, index: usize) -> Abs {
+        self.widths.get(index).copied().unwrap_or(self.width)
+    }
+
+    /// The gap between the column with the given index and the next one.
+    fn gutter_after(&self, index: usize) -> Abs {
+        self.gutters.get(index).copied().unwrap_or(self.gutter)
+    }
+
+    /// The width of all gaps between the columns.
+    fn gutter_total(&self) -> Abs {
+        (1..self.count).map(|i| self.gutter_after(i - 1)).sum()
+    }
+
+    /// The width of all columns and the gutters between them.
+    fn total(&self) -> Abs {
+        let widths = (0..self.count).map(|i| self.width_of(i)).sum::<Abs>();
+        widths + self.gutter_total()
+    }
+
+    /// The available height of the column with the given index in a region
+    /// of the given height.
+    fn height(&self, index: usize, region_height: Abs) -> Abs {
+        match self.heights.get(index) {
+            Some(ratio) => region_height * ratio.get().clamp(0.0, 1.0),
+            None => region_height,
+        }
+    }
+
+    /// The horizontal offset of the column with the given index within a
+    /// region of the given width, taking the direction into account.
+    fn offset(&self, index: usize, region_width: Abs) -> Abs {
+        let start = (0..index)
+            .map(|i| self.width_of(i) + self.gutter_after(i))
+            .sum::<Abs>();
+        let width = self.width_of(index);
+        if self.dir == Dir::LTR { start } else { region_width - start - width }
+    }
 }
 
 /// Configuration of line numbers.
@@ -429,3 +1673,19 @@ impl From<EcoVec<SourceDiagnostic>> for Stop {
         Stop::Error(error)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_passthrough_properties_are_settable() {
+        let params = ColumnsElem::ELEM.params();
+        for &name in PASSTHROUGH_PROPERTIES {
+            assert!(
+                params.iter().any(|param| param.name == name && param.settable),
+                "{name} is not a settable property of columns",
+            );
+        }
+    }
+}
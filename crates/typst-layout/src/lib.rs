@@ -15,6 +15,6 @@ mod shapes;
 mod stack;
 mod transforms;
 
-pub use self::flow::{layout_fragment, layout_frame};
+pub use self::flow::{column_break_candidates, layout_fragment, layout_frame};
 pub use self::pages::layout_document;
 pub use self::rules::register;
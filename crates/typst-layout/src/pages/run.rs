@@ -19,7 +19,7 @@ use typst_library::text::{LocalName, TextElem};
 use typst_library::visualize::Paint;
 use typst_utils::Numeric;
 
-use crate::flow::{FlowMode, layout_flow};
+use crate::flow::{ColumnSettings, FlowMode, layout_flow};
 
 /// A mostly finished layout for one page. Needs only knowledge of its exact
 /// page number to be finalized into a `Page`. (Because the margins can depend
@@ -181,8 +181,22 @@ fn layout_page_run_impl(
         &mut locator,
         styles,
         Regions::repeat(area, area.map(Abs::is_finite)),
-        styles.get(PageElem::columns),
-        styles.get(ColumnsElem::gutter).resolve(styles),
+        ColumnSettings {
+            count: styles.get(PageElem::columns),
+            gutter: styles.get(ColumnsElem::gutter).resolve(styles),
+            trim: styles.get(ColumnsElem::trim),
+            shrink_wrap: styles.get(ColumnsElem::shrink_wrap),
+            wide_child: styles.get(ColumnsElem::wide_child),
+            space_distribution: styles.get(ColumnsElem::space_distribution),
+            prefer_sentence_breaks: styles.get(ColumnsElem::prefer_sentence_breaks),
+            widow_penalty: styles.get(ColumnsElem::widow_penalty),
+            orphan_penalty: styles.get(ColumnsElem::orphan_penalty),
+            colbreak_penalty: styles.get(ColumnsElem::colbreak_penalty),
+            markers: styles.get(ColumnsElem::markers),
+            baseline_grid: styles.resolve(ColumnsElem::baseline_grid),
+            grid_origin: styles.resolve(ColumnsElem::grid_origin),
+            ..ColumnSettings::SINGLE
+        },
         FlowMode::Root,
     )?;
 
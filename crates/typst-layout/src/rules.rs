@@ -10,11 +10,11 @@ use typst_library::foundations::{
 };
 use typst_library::introspection::{Counter, Locator, LocatorLink};
 use typst_library::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, ColumnsElem, Em, GridCell,
-    GridChild, GridElem, GridItem, HAlignment, HElem, HideElem, InlineElem, LayoutElem,
-    Length, MoveElem, OuterVAlignment, PadElem, PlaceElem, PlacementScope, Region, Rel,
-    RepeatElem, RotateElem, ScaleElem, Sides, Size, Sizing, SkewElem, Spacing,
-    StackChild, StackElem, TrackSizings, VElem,
+    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, ColspanElem, ColumnsElem, Em,
+    GridCell, GridChild, GridElem, GridItem, HAlignment, HElem, HideElem, InlineElem,
+    LayoutElem, Length, MoveElem, OuterVAlignment, PadElem, PlaceElem, PlacementScope,
+    Region, Rel, RepeatElem, RotateElem, ScaleElem, Sides, Size, Sizing, SkewElem,
+    Spacing, StackChild, StackElem, TrackSizings, VElem,
 };
 use typst_library::math::EquationElem;
 use typst_library::model::{
@@ -76,6 +76,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Paged, ALIGN_RULE);
     rules.register(Paged, PAD_RULE);
     rules.register(Paged, COLUMNS_RULE);
+    rules.register(Paged, COLSPAN_RULE);
     rules.register(Paged, STACK_RULE);
     rules.register(Paged, GRID_RULE);
     rules.register(Paged, GRID_CELL_RULE);
@@ -699,8 +700,21 @@ const PAD_RULE: ShowFn<PadElem> = |elem, _, _| {
     Ok(BlockElem::multi_layouter(elem.clone(), crate::pad::layout_pad).pack())
 };
 
-const COLUMNS_RULE: ShowFn<ColumnsElem> = |elem, _, _| {
-    Ok(BlockElem::multi_layouter(elem.clone(), crate::flow::layout_columns).pack())
+const COLUMNS_RULE: ShowFn<ColumnsElem> = |elem, _, styles| {
+    let mut block = BlockElem::multi_layouter(elem.clone(), crate::flow::layout_columns);
+    if let Some(gap) = elem.gap_before.get(styles) {
+        block.above.set(Smart::Custom(gap.into()));
+    }
+    if let Some(gap) = elem.gap_after.get(styles) {
+        block.below.set(Smart::Custom(gap.into()));
+    }
+    Ok(block.pack())
+};
+
+const COLSPAN_RULE: ShowFn<ColspanElem> = |elem, _, _| {
+    Ok(BlockElem::new()
+        .with_body(Some(BlockBody::Content(elem.body.clone())))
+        .pack())
 };
 
 const STACK_RULE: ShowFn<StackElem> = |elem, _, _| {
@@ -39,7 +39,7 @@ use crate::foundations::{
 /// #let dict = (fill: blue)
 /// #text(..dict)[Hello]
 /// ```
-#[ty(scope, cast, name = "arguments")]
+#[ty(scope, cast, name = "arguments", operators = ["+"])]
 #[derive(Clone, Hash)]
 #[allow(clippy::derived_hash_with_manual_eq)]
 pub struct Args {
@@ -67,7 +67,7 @@ pub use crate::__array as array;
 /// #(("A", "B", "C")
 ///     .join(", ", last: " and "))
 /// ```
-#[ty(scope, cast)]
+#[ty(scope, cast, operators = ["+", "*", "<", "<=", ">", ">=", "in"], category = "collection", default)]
 #[derive(Default, Clone, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Array(EcoVec<Value>);
@@ -967,39 +967,69 @@ impl Array {
     /// Returns a new array with all duplicate items removed. Only the first
     /// element of each duplicate is kept.
     ///
+    /// By default, items are compared with `{==}`, so values of different
+    /// types that compare equal, like `{1}` and `{1.0}`, are duplicates. To
+    /// compare items differently, e.g. to only treat items of the same type
+    /// as duplicates, provide a comparison function with `by`.
+    ///
     /// ```example
-    /// #(1, 1, 2, 3, 1).dedup()
+    /// #(1, 1, 2, 3, 1).dedup() \
+    /// #(1, 1.0, 2).dedup() \
+    /// #(1, 1.0, 2).dedup(
+    ///   by: (a, b) => type(a) == type(b) and a == b,
+    /// )
     /// ```
     #[func(title = "Deduplicate")]
     pub fn dedup(
         self,
         engine: &mut Engine,
         context: Tracked<Context>,
+        span: Span,
         /// If given, applies this function to the elements in the array to
         /// determine the keys to deduplicate by.
         #[named]
         key: Option<Func>,
+        /// If given, uses this function to determine whether two elements are
+        /// duplicates.
+        ///
+        /// This function should return a boolean: `{true}` indicates that the
+        /// elements are duplicates.
+        ///
+        /// When used together with `key`, `by` will be passed the keys instead
+        /// of the elements.
+        #[named]
+        by: Option<Func>,
     ) -> SourceResult<Array> {
         let mut out = EcoVec::with_capacity(self.0.len());
-        let mut key_of = |x: Value| match &key {
+        let key_of = |engine: &mut Engine, x: Value| match &key {
             // NOTE: We are relying on `comemo`'s memoization of function
             // evaluation to not excessively reevaluate the `key`.
             Some(f) => f.call(engine, context, [x]),
             None => Ok(x),
         };
+        let are_equal = |engine: &mut Engine, x: Value, y: Value| match &by {
+            Some(f) => match f.call(engine, context, [x, y])? {
+                Value::Bool(b) => Ok(b),
+                x => {
+                    bail!(span, "expected boolean from `by` function, got {}", x.ty())
+                }
+            },
+            None => Ok(ops::equal(&x, &y)),
+        };
 
         // This algorithm is O(N^2) because we cannot rely on `HashSet` since:
         // 1. We would like to preserve the order of the elements.
         // 2. We cannot hash arbitrary `Value`.
         'outer: for value in self {
-            let key = key_of(value.clone())?;
+            let key = key_of(engine, value.clone())?;
             if out.is_empty() {
                 out.push(value);
                 continue;
             }
 
             for second in out.iter() {
-                if ops::equal(&key, &key_of(second.clone())?) {
+                let second = key_of(engine, second.clone())?;
+                if are_equal(engine, key.clone(), second)? {
                     continue 'outer;
                 }
             }
@@ -16,7 +16,7 @@ use crate::foundations::{
 /// contextual behaviour. A good example is the [text direction]($text.dir)
 /// parameter. Setting it to `{auto}` lets Typst automatically determine the
 /// direction from the [text language]($text.lang).
-#[ty(cast, name = "auto")]
+#[ty(cast, name = "auto", clone_cost = "cheap")]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct AutoValue;
 
@@ -13,7 +13,7 @@ use crate::foundations::{Repr, ty};
 /// #true \
 /// #(1 < 2)
 /// ```
-#[ty(cast, title = "Boolean")]
+#[ty(cast, title = "Boolean", operators = ["not", "and", "or", "<", "<=", ">", ">="], clone_cost = "cheap", default)]
 type bool;
 
 impl Repr for bool {
@@ -41,7 +41,7 @@ use crate::foundations::{Array, Reflect, Repr, Str, Value, cast, func, scope, ty
 /// #array(data.slice(0, 4)) \
 /// #str(data.slice(1, 4))
 /// ```
-#[ty(scope, cast)]
+#[ty(scope, cast, operators = ["+"])]
 #[derive(Clone, Hash)]
 #[allow(clippy::derived_hash_with_manual_eq)]
 pub struct Bytes(Arc<LazyHash<dyn Bytelike>>);
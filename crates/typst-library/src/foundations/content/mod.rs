@@ -77,7 +77,7 @@ use crate::text::UnderlineElem;
 /// In the web app, you can hover over a content variable to see exactly which
 /// elements the content is composed of and what fields they have.
 /// Alternatively, you can inspect the output of the [`repr`] function.
-#[ty(scope, cast)]
+#[ty(scope, cast, operators = ["+", "*"], category = "textual")]
 #[derive(Clone, PartialEq, Hash)]
 #[repr(transparent)]
 pub struct Content(raw::RawContent);
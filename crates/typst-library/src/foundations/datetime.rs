@@ -11,7 +11,7 @@ use crate::World;
 use crate::diag::{StrResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Dict, Duration, Repr, Smart, Str, Value, cast, func, repr, scope, ty,
+    Dict, Duration, Repr, Smart, Str, Type, Value, cast, func, repr, scope, ty,
 };
 
 /// Represents a date, a time, or a combination of both.
@@ -133,7 +133,7 @@ use crate::foundations::{
 /// will be stored as a plain date internally, meaning that you cannot use
 /// components such as `hour` or `minute`, which would only work on datetimes
 /// that have a specified time.
-#[ty(scope, cast)]
+#[ty(scope, cast, operators = ["+", "-", "<", "<=", ">", ">="], clone_cost = "cheap")]
 #[derive(Debug, Clone, Copy, PartialEq, Hash)]
 pub enum Datetime {
     /// Representation as a date.
@@ -460,7 +460,7 @@ impl Repr for Datetime {
             .flatten()
             .collect::<EcoVec<_>>();
 
-        eco_format!("datetime{}", &repr::pretty_array_like(&filtered, false))
+        repr::constructor_or_literal(Type::of::<Self>(), &filtered)
     }
 }
 
@@ -90,7 +90,14 @@ use crate::foundations::{Repr, Str, cast, func, repr, scope, ty};
 /// push a number's fractional digits beyond the limits described above, leading
 /// to rounding. When those two operations do not surpass the digit limits, they
 /// are fully precise.
-#[ty(scope, cast)]
+#[ty(
+    scope,
+    cast,
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Decimal(rust_decimal::Decimal);
 
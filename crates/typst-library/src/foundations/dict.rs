@@ -65,7 +65,7 @@ pub use crate::__dict as dict;
 /// #dict.insert("city", "Berlin ")
 /// #("name" in dict)
 /// ```
-#[ty(scope, cast, name = "dictionary")]
+#[ty(scope, cast, name = "dictionary", operators = ["+", "in"], category = "collection", default)]
 #[derive(Default, Clone, PartialEq)]
 pub struct Dict(Arc<IndexMap<Str, Value, FxBuildHasher>>);
 
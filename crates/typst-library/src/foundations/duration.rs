@@ -4,10 +4,10 @@ use std::ops::{Add, Div, Mul, Neg, Sub};
 use ecow::{EcoString, eco_format};
 use time::ext::NumericalDuration;
 
-use crate::foundations::{Repr, func, repr, scope, ty};
+use crate::foundations::{Repr, Type, func, repr, scope, ty};
 
 /// Represents a positive or negative span of time.
-#[ty(scope, cast)]
+#[ty(scope, cast, operators = ["+", "-", "*", "/", "<", "<=", ">", ">="], clone_cost = "cheap")]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Duration(time::Duration);
 
@@ -156,7 +156,7 @@ impl Repr for Duration {
             vec.push(eco_format!("seconds: {}", seconds.repr()));
         }
 
-        eco_format!("duration{}", &repr::pretty_array_like(&vec, false))
+        repr::constructor_or_literal(Type::of::<Self>(), &vec)
     }
 }
 
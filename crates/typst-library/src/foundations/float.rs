@@ -25,7 +25,15 @@ use crate::layout::Ratio;
 /// #1e4 \
 /// #(10 / 4)
 /// ```
-#[ty(scope, cast, name = "float")]
+#[ty(
+    scope,
+    cast,
+    name = "float",
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 type f64;
 
 #[scope]
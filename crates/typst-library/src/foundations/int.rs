@@ -36,7 +36,16 @@ use crate::foundations::{
 /// #0o10 \
 /// #0b1001
 /// ```
-#[ty(scope, cast, name = "int", title = "Integer")]
+#[ty(
+    scope,
+    cast,
+    name = "int",
+    title = "Integer",
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 type i64;
 
 #[scope]
@@ -46,7 +46,7 @@ use crate::foundations::{Repr, Str, bail, func, scope, ty};
 ///
 /// Currently, labels can only be attached to elements in markup mode, not in
 /// code mode. This might change in the future.
-#[ty(scope, cast)]
+#[ty(scope, cast, clone_cost = "cheap")]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Label(PicoStr);
 
@@ -108,6 +108,7 @@ pub(super) fn define(global: &mut Scope, inputs: Dict, features: &Features) {
     global.define_type::<Duration>();
     global.define_type::<Version>();
     global.define_func::<repr::repr>();
+    global.define_func::<repr::inspect>();
     global.define_func::<panic>();
     global.define_func::<assert>();
     global.define_func::<eval>();
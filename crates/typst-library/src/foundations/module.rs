@@ -45,7 +45,7 @@ use crate::foundations::{Content, Scope, Value, repr, ty};
 /// Alternatively, it is possible to convert a module to a dictionary, and
 /// therefore access its contents dynamically, using the [dictionary
 /// constructor]($dictionary/#constructor).
-#[ty(cast)]
+#[ty(cast, operators = ["in"])]
 #[derive(Clone, Hash)]
 #[allow(clippy::derived_hash_with_manual_eq)]
 pub struct Module {
@@ -20,7 +20,7 @@ use crate::foundations::{
 /// ```example
 /// Not visible: #none
 /// ```
-#[ty(cast, name = "none")]
+#[ty(cast, name = "none", clone_cost = "cheap")]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct NoneValue;
 
@@ -1,13 +1,15 @@
 //! Operations on values.
 
 use std::cmp::Ordering;
+use std::sync::{Arc, OnceLock};
 
 use ecow::eco_format;
+use rustc_hash::FxHashMap;
 use typst_utils::Numeric;
 
 use crate::diag::{HintedStrResult, StrResult, bail};
 use crate::foundations::{
-    Datetime, IntoValue, Regex, Repr, SymbolElem, Value, format_str,
+    Datetime, Dynamic, IntoValue, Regex, Repr, SymbolElem, Type, Value, format_str,
 };
 use crate::layout::{Alignment, Length, Rel};
 use crate::text::TextElem;
@@ -418,8 +420,83 @@ comparison!(leq, "<=", Ordering::Less | Ordering::Equal);
 comparison!(gt, ">", Ordering::Greater);
 comparison!(geq, ">=", Ordering::Greater | Ordering::Equal);
 
+/// A custom equality comparison for dynamic values of a specific type.
+pub type EqualityFn = Arc<dyn Fn(&Dynamic, &Dynamic) -> bool + Send + Sync>;
+
+/// Custom equality comparisons for dynamic values, keyed by the type of the
+/// values they compare.
+///
+/// Comparisons are collected in a registry and then [installed](Self::install)
+/// once for the whole process. From then on, [`equal`] (and thus `==` in
+/// Typst) uses a comparison when both values are dynamic values of its type
+/// instead of their structural equality. This lets library authors define
+/// semantic equality, e.g. to ignore a cache field.
+///
+/// The comparisons must be consistent with hashing: Values that a comparison
+/// considers equal should hash the same.
+#[derive(Default, Clone)]
+pub struct Equalities(FxHashMap<Type, EqualityFn>);
+
+/// The installed custom equality comparisons.
+static EQUALITIES: OnceLock<Equalities> = OnceLock::new();
+
+impl Equalities {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a custom equality comparison for values of the given type.
+    ///
+    /// Returns the previously registered comparison, if any.
+    pub fn register(
+        &mut self,
+        ty: Type,
+        eq: impl Fn(&Dynamic, &Dynamic) -> bool + Send + Sync + 'static,
+    ) -> Option<EqualityFn> {
+        self.0.insert(ty, Arc::new(eq))
+    }
+
+    /// Compares two dynamic values with the registered comparison for their
+    /// type, if they are of the same type and there is one.
+    pub fn compare(&self, lhs: &Dynamic, rhs: &Dynamic) -> Option<bool> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let ty = lhs.ty();
+        if ty != rhs.ty() {
+            return None;
+        }
+
+        self.0.get(&ty).map(|eq| eq(lhs, rhs))
+    }
+
+    /// Installs the comparisons for the whole process.
+    ///
+    /// This must happen before the first compilation: Since the results of
+    /// compilations are cached, the comparisons can't change afterwards. The
+    /// registry is frozen once dynamic values were first compared, so this
+    /// fails and returns the registry if it is called after that or a second
+    /// time.
+    pub fn install(self) -> Result<(), Self> {
+        EQUALITIES.set(self)
+    }
+
+    /// The installed comparisons. Freezes them if none were installed yet.
+    fn installed() -> &'static Self {
+        EQUALITIES.get_or_init(Self::default)
+    }
+}
+
 /// Determine whether two values are equal.
 pub fn equal(lhs: &Value, rhs: &Value) -> bool {
+    equal_with(lhs, rhs, Equalities::installed())
+}
+
+/// Determine whether two values are equal, comparing dynamic values with the
+/// given custom comparisons.
+fn equal_with(lhs: &Value, rhs: &Value, equalities: &Equalities) -> bool {
     use Value::*;
     match (lhs, rhs) {
         // Compare reflexively.
@@ -441,15 +518,23 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Bytes(a), Bytes(b)) => a == b,
         (Label(a), Label(b)) => a == b,
         (Content(a), Content(b)) => a == b,
-        (Array(a), Array(b)) => a == b,
-        (Dict(a), Dict(b)) => a == b,
+        (Array(a), Array(b)) => {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(x, y)| equal_with(x, y, equalities))
+        }
+        (Dict(a), Dict(b)) => {
+            a.len() == b.len()
+                && a.iter().all(|(key, x)| {
+                    b.get(key).is_ok_and(|y| equal_with(x, y, equalities))
+                })
+        }
         (Func(a), Func(b)) => a == b,
         (Args(a), Args(b)) => a == b,
         (Type(a), Type(b)) => a == b,
         (Module(a), Module(b)) => a == b,
         (Datetime(a), Datetime(b)) => a == b,
         (Duration(a), Duration(b)) => a == b,
-        (Dyn(a), Dyn(b)) => a == b,
+        (Dyn(a), Dyn(b)) => equalities.compare(a, b).unwrap_or_else(|| a == b),
 
         // Some technically different things should compare equal.
         (&Int(i), &Float(f)) | (&Float(f), &Int(i)) => i as f64 == f,
@@ -482,6 +567,7 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         (Fraction(a), Fraction(b)) => a.cmp(b),
         (Version(a), Version(b)) => a.cmp(b),
         (Str(a), Str(b)) => a.cmp(b),
+        (Type(a), Type(b)) => a.cmp(b),
 
         // Some technically different things should be comparable.
         (Int(a), Float(b)) => try_cmp_values(&(*a as f64), b)?,
@@ -569,3 +655,77 @@ pub fn contains(lhs: &Value, rhs: &Value) -> Option<bool> {
 fn too_large() -> &'static str {
     "value is too large"
 }
+
+#[cfg(test)]
+mod tests {
+    use ecow::EcoString;
+
+    use super::*;
+    use crate::foundations::{Array, ty};
+
+    /// A fake domain object whose cache shouldn't affect equality.
+    #[ty]
+    #[derive(Debug, Clone, PartialEq, Hash)]
+    struct Cached {
+        value: i64,
+        cache: i64,
+    }
+
+    impl Repr for Cached {
+        fn repr(&self) -> EcoString {
+            "cached".into()
+        }
+    }
+
+    /// Another fake type.
+    #[ty]
+    #[derive(Debug, Clone, PartialEq, Hash)]
+    struct Other;
+
+    impl Repr for Other {
+        fn repr(&self) -> EcoString {
+            "other".into()
+        }
+    }
+
+    #[test]
+    fn test_register_equality() {
+        let a = Cached { value: 1, cache: 1 };
+        let b = Cached { value: 1, cache: 2 };
+        let c = Cached { value: 2, cache: 1 };
+        let [a, b, c] = [a, b, c].map(Dynamic::new);
+
+        let mut equalities = Equalities::new();
+        assert_eq!(equalities.compare(&a, &b), None);
+
+        let previous = equalities.register(Type::of::<Cached>(), |lhs, rhs| {
+            let value = |v: &Dynamic| v.downcast::<Cached>().unwrap().value;
+            value(lhs) == value(rhs)
+        });
+        assert!(previous.is_none());
+        assert_eq!(equalities.compare(&a, &b), Some(true));
+        assert_eq!(equalities.compare(&a, &c), Some(false));
+
+        // Values of other types are unaffected.
+        let other = Dynamic::new(Other);
+        assert_eq!(equalities.compare(&a, &other), None);
+    }
+
+    #[test]
+    fn test_equal_with_registered_equality() {
+        let a = Value::dynamic(Cached { value: 1, cache: 1 });
+        let b = Value::dynamic(Cached { value: 1, cache: 2 });
+        let nested = |v: &Value| Value::Array(Array::from_iter([v.clone()]));
+
+        let mut equalities = Equalities::new();
+        assert!(!equal_with(&a, &b, &equalities));
+
+        equalities.register(Type::of::<Cached>(), |lhs, rhs| {
+            let value = |v: &Dynamic| v.downcast::<Cached>().unwrap().value;
+            value(lhs) == value(rhs)
+        });
+        assert!(equal_with(&a, &b, &equalities));
+        assert!(equal_with(&nested(&a), &nested(&b), &equalities));
+        assert!(!equal_with(&a, &Value::dynamic(Other), &equalities));
+    }
+}
@@ -3,7 +3,8 @@
 use ecow::{EcoString, eco_format};
 use typst_utils::round_with_precision;
 
-use crate::foundations::{Str, Value, func};
+use crate::foundations::{Content, NativeElement, Str, Type, Value, func};
+use crate::text::{RawContent, RawElem};
 
 /// The Unicode minus sign.
 pub const MINUS_SIGN: &str = "\u{2212}";
@@ -32,6 +33,70 @@ pub fn repr(
     value.repr().into()
 }
 
+/// Shows a value together with its type, for debugging.
+///
+/// The result displays the value's type and its [representation]($repr). For
+/// arrays and dictionaries, it additionally shows a tree of all contained
+/// values, where each entry is annotated with its type.
+///
+/// **Note:** Like `repr`, this function is for debugging purposes. Its output
+/// should not be considered stable and may change at any time!
+///
+/// # Example
+/// ```example
+/// #inspect((name: "Typst", tags: ("fast", 1)))
+/// ```
+#[func]
+pub fn inspect(
+    /// The value to inspect.
+    value: Value,
+) -> Content {
+    let mut text = eco_format!("{}\n{}", value.ty().long_name(), value.repr());
+    if matches!(value, Value::Array(_) | Value::Dict(_)) {
+        text.push('\n');
+        text.push_str(&debug_tree(&value));
+    }
+    RawElem::new(RawContent::Text(text)).with_block(true).pack()
+}
+
+/// Formats the items of an array or dictionary as a tree, with the type of
+/// each item. Nested containers are indented below their key.
+pub fn debug_tree(value: &Value) -> EcoString {
+    fn walk(value: &Value, depth: usize, buf: &mut EcoString) {
+        let mut entry = |key: EcoString, value: &Value| {
+            buf.push_str(&"  ".repeat(depth));
+            buf.push_str(&eco_format!("{key}: {}", value.ty().long_name()));
+            if matches!(value, Value::Array(_) | Value::Dict(_)) {
+                buf.push('\n');
+                walk(value, depth + 1, buf);
+            } else {
+                buf.push_str(&eco_format!(" = {}\n", value.repr()));
+            }
+        };
+
+        match value {
+            Value::Array(array) => {
+                for (i, item) in array.iter().enumerate() {
+                    entry(eco_format!("{i}"), item);
+                }
+            }
+            Value::Dict(dict) => {
+                for (key, item) in dict.iter() {
+                    entry(key.as_str().into(), item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut buf = EcoString::new();
+    walk(value, 0, &mut buf);
+    if buf.ends_with('\n') {
+        buf.pop();
+    }
+    buf
+}
+
 /// A trait that defines the `repr` of a Typst value.
 pub trait Repr {
     /// Return the debug representation of the value.
@@ -208,9 +273,44 @@ pub fn pretty_array_like(parts: &[impl AsRef<str>], trailing_comma: bool) -> Str
     buf
 }
 
+/// Formats a value of the given type for code mode, deciding between a call
+/// to the type's constructor and a literal.
+///
+/// If the type has a constructor, the value is represented as a call to it
+/// with the given, already formatted arguments, e.g. `version(1, 2)`.
+/// Otherwise, no call could reproduce the value and the arguments alone are
+/// shown as an array or dictionary literal holding the same data.
+pub fn constructor_or_literal(ty: Type, args: &[impl AsRef<str>]) -> EcoString {
+    if ty.has_constructor() {
+        eco_format!("{}{}", ty.short_name(), pretty_array_like(args, false))
+    } else {
+        // A single element needs a trailing comma to not be read as a
+        // parenthesized expression.
+        pretty_array_like(args, args.len() == 1).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::foundations::{Content, Duration, Repr, Version};
+
+    #[test]
+    fn test_constructor_or_literal() {
+        let version = Type::of::<Version>();
+        let content = Type::of::<Content>();
+        assert_eq!(constructor_or_literal(version, &["1", "2"]), "version(1, 2)");
+        assert_eq!(constructor_or_literal(version, &["1"]), "version(1)");
+        assert_eq!(constructor_or_literal(content, &["1", "2"]), "(1, 2)");
+        assert_eq!(constructor_or_literal(content, &["1"]), "(1,)");
+        assert_eq!(constructor_or_literal(content, &["a: 1"]), "(a: 1,)");
+    }
+
+    #[test]
+    fn test_constructor_reprs() {
+        assert_eq!(Version::from_iter([1, 2]).repr(), "version(1, 2)");
+        assert_eq!(Duration::from(time::Duration::days(2)).repr(), "duration(days: 2)");
+    }
 
     #[test]
     fn test_to_base() {
@@ -199,6 +199,8 @@ impl Scope {
     }
 
     /// Try to access a binding immutably.
+    ///
+    /// This is a hash map lookup and thus runs in constant time.
     pub fn get(&self, var: &str) -> Option<&Binding> {
         self.map.get(var)
     }
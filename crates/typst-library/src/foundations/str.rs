@@ -5,16 +5,18 @@ use std::ops::{Add, AddAssign, Deref, Range};
 
 use comemo::Tracked;
 use ecow::EcoString;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use typst_syntax::{Span, Spanned};
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::diag::{At, SourceResult, StrResult, bail};
+use crate::World;
+use crate::diag::{At, HintedStrResult, SourceResult, StrResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Array, Bytes, Cast, Context, Decimal, Dict, Func, IntoValue, Label, Repr, Type,
-    Value, Version, cast, dict, func, repr, scope, ty,
+    Array, Bytes, Cast, CastInfo, Context, Decimal, Dict, FromValue, Func, IntoValue,
+    Label, Reflect, Repr, Type, Value, Version, cast, dict, func, repr, scope, ty,
 };
 use crate::layout::Alignment;
 
@@ -69,7 +71,14 @@ pub use crate::__format_str as format_str;
 /// - `[\r]` for a carriage return
 /// - `[\t]` for a tab
 /// - `[\u{1f600}]` for a hexadecimal Unicode escape sequence
-#[ty(scope, cast, title = "String")]
+#[ty(
+    scope,
+    cast,
+    title = "String",
+    operators = ["+", "*", "<", "<=", ">", ">=", "in"],
+    category = "textual",
+    default,
+)]
 #[derive(Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[derive(Serialize, Deserialize)]
 #[serde(transparent)]
@@ -149,27 +158,33 @@ impl Str {
     /// ```
     #[func(constructor)]
     pub fn construct(
+        engine: &mut Engine,
         /// The value that should be converted to a string.
-        value: ToStr,
+        value: Spanned<ToStr>,
         /// The base (radix) to display integers in, between 2 and 36.
         #[named]
         #[default(Spanned::new(10, Span::detached()))]
         base: Spanned<i64>,
     ) -> SourceResult<Str> {
-        Ok(match value {
-            ToStr::Str(s) => {
-                if base.v != 10 {
-                    bail!(base.span, "base is only supported for integers");
-                }
-                s
-            }
+        let s = match value.v {
+            ToStr::Str(s) => s,
             ToStr::Int(n) => {
                 if base.v < 2 || base.v > 36 {
                     bail!(base.span, "base must be between 2 and 36");
                 }
-                repr::format_int_with_base(n, base.v).into()
+                return Ok(repr::format_int_with_base(n, base.v).into());
             }
-        })
+            ToStr::Other(v) => match engine.world.library().str_formatters.format(&v) {
+                Some(result) => result.at(value.span)?,
+                None => return Err(ToStr::error(&v)).at(value.span),
+            },
+        };
+
+        if base.v != 10 {
+            bail!(base.span, "base is only supported for integers");
+        }
+
+        Ok(s)
     }
 
     /// The length of the string in UTF-8 encoded bytes.
@@ -815,6 +830,9 @@ pub enum ToStr {
     Str(Str),
     /// An integer about to be formatted in a given base.
     Int(i64),
+    /// A value without a native conversion, to be formatted with the
+    /// library's [`StrFormatRegistry`].
+    Other(Value),
 }
 
 cast! {
@@ -827,6 +845,83 @@ cast! {
     v: Label => Self::Str(v.resolve().as_str().into()),
     v: Type => Self::Str(v.long_name().into()),
     v: Str => Self::Str(v),
+    v: Unconverted => Self::Other(v.0),
+}
+
+/// Converts a value of some type to a string.
+pub type StrFormatter = fn(&Value) -> StrResult<Str>;
+
+/// String conversions for types without a native one, consulted by the `str`
+/// constructor.
+///
+/// Types with a native conversion, like integers or labels, always use it. For
+/// other types, the formatter registered in the library's registry is used
+/// (see [`LibraryBuilder::with_str_formatters`]).
+///
+/// [`LibraryBuilder::with_str_formatters`]: crate::LibraryBuilder::with_str_formatters
+#[derive(Debug, Default, Clone)]
+pub struct StrFormatRegistry(FxHashMap<Type, StrFormatter>);
+
+impl StrFormatRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a formatter for a type. Returns the previously registered
+    /// formatter, if any.
+    pub fn register(
+        &mut self,
+        ty: Type,
+        formatter: StrFormatter,
+    ) -> Option<StrFormatter> {
+        self.0.insert(ty, formatter)
+    }
+
+    /// The formatter registered for a type, if any.
+    pub fn get(&self, ty: Type) -> Option<StrFormatter> {
+        self.0.get(&ty).copied()
+    }
+
+    /// Formats a value with the formatter registered for its type. Returns
+    /// `None` if there is no such formatter.
+    pub fn format(&self, value: &Value) -> Option<StrResult<Str>> {
+        self.get(value.ty()).map(|formatter| formatter(value))
+    }
+}
+
+impl Hash for StrFormatRegistry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_by_key(|(ty, _)| **ty);
+        entries.hash(state);
+    }
+}
+
+/// A value without a native string conversion.
+///
+/// It doesn't show up in the accepted types as whether it can be converted
+/// depends on the library's [`StrFormatRegistry`].
+struct Unconverted(Value);
+
+impl Reflect for Unconverted {
+    fn input() -> CastInfo {
+        CastInfo::Union(vec![])
+    }
+
+    fn output() -> CastInfo {
+        Str::output()
+    }
+
+    fn castable(_: &Value) -> bool {
+        true
+    }
+}
+
+impl FromValue for Unconverted {
+    fn from_value(value: Value) -> HintedStrResult<Self> {
+        Ok(Self(value))
+    }
 }
 
 /// A Unicode normalization form.
@@ -1032,3 +1127,30 @@ cast! {
     v: Str => Self::Str(v),
     v: Func => Self::Func(v)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A type without a native string conversion.
+    #[ty]
+    #[derive(Debug, Clone, PartialEq, Hash)]
+    struct Fake;
+
+    impl Repr for Fake {
+        fn repr(&self) -> EcoString {
+            "fake".into()
+        }
+    }
+
+    #[test]
+    fn test_str_format_registry() {
+        let value = Value::dynamic(Fake);
+        let mut registry = StrFormatRegistry::new();
+        assert!(registry.format(&value).is_none());
+
+        registry.register(Type::of::<Fake>(), |_| Ok("custom".into()));
+        assert_eq!(registry.format(&value).unwrap().unwrap(), "custom".into());
+        assert!(registry.format(&Value::Int(1)).is_none());
+    }
+}
@@ -2,16 +2,26 @@
 pub use typst_macros::{scope, ty};
 
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt::{self, Debug, Display, Formatter};
-use std::sync::LazyLock;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, LazyLock};
 
+use comemo::Tracked;
 use ecow::{EcoString, eco_format};
+use rustc_hash::{FxHashMap, FxHashSet};
+use typst_syntax::Span;
 use typst_utils::Static;
 
-use crate::diag::{DeprecationSink, StrResult, bail};
+use crate::diag::{At, DeprecationSink, SourceResult, StrResult, bail};
+use crate::engine::Engine;
 use crate::foundations::{
-    AutoValue, Func, NativeFuncData, NoneValue, Repr, Scope, Value, cast, func,
+    Array, AutoValue, Binding, Cast, CastInfo, Context, Dict, Func, IntoValue,
+    NativeFuncData, NoneValue, Regex, Repr, Scope, Str, Value, ValueKind, cast, dict,
+    func, ops,
 };
+use crate::layout::{Abs, Angle, Em, Length, Ratio};
+use crate::visualize::Color;
 
 /// Describes a kind of value.
 ///
@@ -58,16 +68,62 @@ use crate::foundations::{
 /// Note that `type` will return [`content`] for all document elements. To
 /// programmatically determine which kind of content you are dealing with, see
 /// [`content.func`].
-#[ty(scope, cast)]
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+///
+/// # Operators
+/// The `operators` field of a type lists the operators its values support,
+/// spelled as in code. Equality is not listed, as all values support `==`.
+/// ```example
+/// #int.operators \
+/// #str.operators
+/// ```
+///
+/// # Units
+/// The `unit` field of a type holds the suffix with which its values are
+/// displayed by default, or `{none}` for types without units.
+/// ```example
+/// #length.unit \
+/// #angle.unit
+/// ```
+///
+/// # Callable types
+/// The `is-callable` field of a type indicates whether its values can be
+/// called. This is the case for [functions]($function) and for types
+/// themselves, which are called to construct values.
+/// ```example
+/// #type(x => x).is-callable \
+/// #type(1).is-callable \
+/// #type.is-callable
+/// ```
+///
+/// # Ordering
+/// Types can be compared with `<`, `<=`, `>`, and `>=`, which order them by
+/// their long names. This makes it possible to sort arrays of types.
+/// ```example
+/// #(str, int, float).sorted()
+/// ```
+#[ty(scope, cast, operators = ["<", "<=", ">", ">="], clone_cost = "cheap")]
+#[derive(Copy, Clone)]
 pub struct Type(Static<NativeTypeData>);
 
 impl Type {
     /// Get the type for `T`.
+    ///
+    /// This works for all Rust types that are exposed to Typst, i.e. those
+    /// annotated with `#[ty]`. To get the type of a runtime value instead, use
+    /// [`Value::ty`].
     pub fn of<T: NativeType>() -> Self {
         T::ty()
     }
 
+    /// Get the short name and the type for `T` together.
+    ///
+    /// This is a convenience for registration code, which typically needs
+    /// both, e.g. to define the type in a scope under its name.
+    pub fn reflect<T: NativeType>() -> (&'static str, Self) {
+        let ty = Self::of::<T>();
+        (ty.short_name(), ty)
+    }
+
     /// The type's short name, how it is used in code (e.g. `str`).
     pub fn short_name(&self) -> &'static str {
         self.0.name
@@ -83,6 +139,18 @@ impl Type {
         self.0.title
     }
 
+    /// A URL-safe identifier for the type, e.g. for anchors in generated
+    /// documentation.
+    ///
+    /// This is the lowercased short name with all characters that are not
+    /// ASCII letters or digits replaced by hyphens.
+    pub fn slug(&self) -> EcoString {
+        self.short_name()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+            .collect()
+    }
+
     /// Documentation for the type (as Markdown).
     pub fn docs(&self) -> &'static str {
         self.0.docs
@@ -93,31 +161,490 @@ impl Type {
         self.0.keywords
     }
 
+    /// The first sentence of the type's documentation, with line breaks
+    /// replaced by spaces.
+    pub fn docs_summary(&self) -> EcoString {
+        let docs = self.docs();
+        let paragraph = docs.split("\n\n").next().unwrap_or_default();
+        let mut depth = 0;
+        let mut period = false;
+        let mut end = paragraph.len();
+        for (i, c) in paragraph.char_indices() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                '.' if depth == 0 => period = true,
+                c if period && c.is_whitespace() && !docs[..i].ends_with("e.g.") => {
+                    end = i;
+                    break;
+                }
+                _ => period = false,
+            }
+        }
+        EcoString::from(&docs[..end]).replace("\r\n", " ").replace("\n", " ")
+    }
+
+    /// A one-line, human-readable description of the type and its
+    /// constructor, e.g. for hover tooltips in an IDE.
+    ///
+    /// The line consists of the type's name, the summary of its
+    /// documentation, and, if it has a constructor, the constructor's
+    /// parameters together with the types they accept.
+    pub fn signature_string(&self) -> EcoString {
+        let mut string = eco_format!("{} — {}", self.long_name(), self.docs_summary());
+        let Some(params) = self.constructor_opt().and_then(|func| func.params()) else {
+            return string;
+        };
+
+        let params: Vec<_> = params
+            .iter()
+            .map(|param| {
+                let mut types: Vec<EcoString> = vec![];
+                param.input.walk(|info| {
+                    let name = match info {
+                        CastInfo::Any => "any".into(),
+                        CastInfo::Value(value, _) => value.repr(),
+                        CastInfo::Type(ty) => ty.short_name().into(),
+                        CastInfo::Union(_) => return,
+                    };
+                    if !types.contains(&name) {
+                        types.push(name);
+                    }
+                });
+                let prefix = if param.variadic { ".." } else { "" };
+                eco_format!("{prefix}{}: {}", param.name, types.join(" | "))
+            })
+            .collect();
+
+        string.push_str(&eco_format!(" ({})", params.join(", ")));
+        string
+    }
+
+    /// The name of each parameter of the type's constructor together with the
+    /// types it accepts, e.g. for overload hints in an IDE.
+    ///
+    /// A parameter that accepts specific values, like a set of strings,
+    /// accepts the types of these values. An empty set of types means that
+    /// the parameter accepts any value. Returns `None` if the type has no
+    /// constructor.
+    pub fn constructor_param_types(&self) -> Option<Vec<(&'static str, Vec<Type>)>> {
+        let params = self.constructor_opt()?.params()?;
+        Some(
+            params
+                .iter()
+                .map(|param| {
+                    let mut types = vec![];
+                    param.input.walk(|info| {
+                        let ty = match info {
+                            CastInfo::Type(ty) => *ty,
+                            CastInfo::Value(value, _) => value.ty(),
+                            CastInfo::Any | CastInfo::Union(_) => return,
+                        };
+                        if !types.contains(&ty) {
+                            types.push(ty);
+                        }
+                    });
+                    (param.name, types)
+                })
+                .collect(),
+        )
+    }
+
+    /// The type of values of the given kind. Returns `None` for
+    /// [`ValueKind::Dyn`], as dynamic values can be of many different types.
+    pub fn from_kind(kind: ValueKind) -> Option<Type> {
+        kind.ty()
+    }
+
+    /// The kind of the values of this type. Returns `None` if its values are
+    /// dynamic, i.e. of kind [`ValueKind::Dyn`].
+    pub fn kind(&self) -> Option<ValueKind> {
+        ValueKind::ALL.iter().copied().find(|kind| kind.ty() == Some(*self))
+    }
+
+    /// The set of numeric types of the standard library: `int`, `float`,
+    /// `decimal`, `length`, `angle`, `ratio`, `relative`, and `fraction`.
+    ///
+    /// The set is derived from the types' [categories](Self::category), so
+    /// new numeric types only need to be marked with `category = "numeric"`
+    /// in their `#[ty]` attribute.
+    pub fn numeric_set() -> &'static FxHashSet<Type> {
+        static NUMERIC: LazyLock<FxHashSet<Type>> = LazyLock::new(|| {
+            Type::all().iter().copied().filter(Type::is_numeric).collect()
+        });
+        &NUMERIC
+    }
+
+    /// Whether this is a numeric type, i.e. one of the
+    /// [`TypeCategory::Numeric`] category.
+    pub fn is_numeric(&self) -> bool {
+        self.category() == TypeCategory::Numeric
+    }
+
+    /// Whether the value would be accepted where a value of this type is
+    /// expected, e.g. by a function parameter.
+    ///
+    /// This applies the same coercions as the argument parser, so it is
+    /// broader than comparing types: An integer is compatible with `float`,
+    /// a string with `content`, and a length with `stroke`.
+    pub fn is_compatible(&self, value: &Value) -> bool {
+        (self.0.castable)(value)
+    }
+
+    /// A few illustrative values of this type, e.g. for an example picker in
+    /// a playground.
+    ///
+    /// The examples are not exhaustive and only exist for common types. For
+    /// all other types, the slice is empty.
+    pub fn example_values(&self) -> &'static [Value] {
+        static EXAMPLES: LazyLock<FxHashMap<Type, Vec<Value>>> = LazyLock::new(|| {
+            FxHashMap::from_iter([
+                (Type::of::<bool>(), vec![Value::Bool(true), Value::Bool(false)]),
+                (Type::of::<i64>(), vec![Value::Int(0), Value::Int(1), Value::Int(42)]),
+                (
+                    Type::of::<f64>(),
+                    vec![Value::Float(0.0), Value::Float(0.5), Value::Float(2.5)],
+                ),
+                (Type::of::<Str>(), vec!["".into_value(), "Hello".into_value()]),
+                (
+                    Type::of::<Length>(),
+                    vec![Abs::pt(12.0).into_value(), Em::new(1.5).into_value()],
+                ),
+                (Type::of::<Ratio>(), vec![Ratio::new(0.5).into_value()]),
+                (Type::of::<Angle>(), vec![Angle::deg(90.0).into_value()]),
+                (Type::of::<Color>(), vec![Color::BLACK.into_value()]),
+                (Type::of::<Array>(), vec![Array::new().into_value()]),
+                (Type::of::<Dict>(), vec![Dict::new().into_value()]),
+            ])
+        });
+        EXAMPLES.get(self).map_or(&[], Vec::as_slice)
+    }
+
+    /// The operators that values of this type support, spelled as in Typst
+    /// code.
+    ///
+    /// Arithmetic operators are `+`, `-`, `*`, and `/`, where `+` and `-`
+    /// include the unary forms if the type supports them. Comparison
+    /// operators are `<`, `<=`, `>`, and `>=`. Equality is not listed, as
+    /// all values can be compared with `==`. The logical operators are
+    /// `not`, `and`, and `or`. Finally, `in` means that values of this type
+    /// can be searched with `in` and `not in`. An operator may only work
+    /// with specific types on the other side, e.g. `*` for strings needs an
+    /// integer.
+    pub fn supported_operators(&self) -> &'static [&'static str] {
+        self.0.operators
+    }
+
+    /// The unit suffix with which values of this type are displayed by
+    /// default, for types with units.
+    ///
+    /// This is `pt` for lengths, `deg` for angles, `fr` for fractions, and
+    /// `%` for ratios. Types without units, like integers, have no suffix.
+    pub fn unit_suffix(&self) -> Option<&'static str> {
+        self.0.unit
+    }
+
+    /// How expensive it is to clone values of this type.
+    ///
+    /// Arrays and dictionaries are [`CloneCost::Shared`] as they are only
+    /// copied once a clone is mutated (copy-on-write).
+    pub fn clone_cost(&self) -> CloneCost {
+        self.0.clone_cost
+    }
+
+    /// The broad category of this type.
+    ///
+    /// Types that don't fit into any of the categories are
+    /// [`TypeCategory::Other`].
+    pub fn category(&self) -> TypeCategory {
+        self.0.category
+    }
+
+    /// The canonical empty or zero value of this type, e.g. `{0}` for
+    /// integers or `{()}` for arrays.
+    pub fn default_value(&self) -> StrResult<Value> {
+        self.0
+            .default
+            .map(|default| default())
+            .ok_or_else(|| eco_format!("type {self} does not have a default value"))
+    }
+
+    /// Whether values of this type can be called like a function.
+    ///
+    /// This holds for functions, including closures and element functions,
+    /// as well as for types themselves: Calling a type invokes its
+    /// constructor, e.g. `{int("1")}`. Values of all other types can't be
+    /// called. Note that calling a type fails if it has no constructor.
+    pub fn is_callable(&self) -> bool {
+        *self == Type::of::<Func>() || *self == Type::of::<Type>()
+    }
+
+    /// Whether values of this type can be used as dictionary keys.
+    ///
+    /// Currently, only strings can be keys. Other values, like labels, can
+    /// be converted with `str` first. If more key types are supported in the
+    /// future, they are added here, so that tools checking keys ahead of
+    /// time stay in sync with dictionaries.
+    pub fn is_valid_dict_key(&self) -> bool {
+        *self == Type::of::<Str>()
+    }
+
+    /// A field describing the type itself rather than a definition in its
+    /// scope, i.e. `operators`, `unit`, or `is-callable`.
+    /// Definitions in the scope take precedence over these fields.
+    pub(crate) fn reflection_field(&self, field: &str) -> Option<Value> {
+        match field {
+            "operators" => Some(
+                self.supported_operators()
+                    .iter()
+                    .map(|&op| op.into_value())
+                    .collect::<Array>()
+                    .into_value(),
+            ),
+            "unit" => Some(self.unit_suffix().into_value()),
+            "is-callable" => Some(self.is_callable().into_value()),
+            _ => None,
+        }
+    }
+
+    /// Whether this type has a constructor function.
+    pub fn has_constructor(&self) -> bool {
+        self.0.constructor.is_some()
+    }
+
     /// This type's constructor function.
     pub fn constructor(&self) -> StrResult<Func> {
-        self.0
-            .constructor
-            .as_ref()
-            .map(|lazy| Func::from(*lazy))
+        self.constructor_opt()
             .ok_or_else(|| eco_format!("type {self} does not have a constructor"))
     }
 
+    /// This type's constructor function, if it has one.
+    pub fn constructor_opt(&self) -> Option<Func> {
+        #[cfg(test)]
+        if let Some(stub) = testing::constructor_override(*self) {
+            return Some(stub);
+        }
+
+        self.0.constructor.as_ref().map(|lazy| Func::from(*lazy))
+    }
+
+    /// All types defined in the standard library, including the ones nested
+    /// in the scopes of other definitions.
+    ///
+    /// The types are in definition order and each type is listed once.
+    /// Types that are only available with the HTML feature are not included.
+    pub fn all() -> &'static [Type] {
+        &StandardTypes::get().all
+    }
+
+    /// Whether the given global name is a deprecated alias for a type, like
+    /// `pattern` for `tiling`.
+    ///
+    /// A type value doesn't know the name it was accessed through, so this
+    /// takes the name instead.
+    pub fn is_deprecated_alias(name: &str) -> bool {
+        StandardTypes::get().aliases.contains_key(name)
+    }
+
+    /// Resolves a global name to the canonical type it refers to.
+    ///
+    /// For a deprecated alias, this is the type that replaces it. Returns
+    /// `None` if the name doesn't refer to a type of the standard library.
+    pub fn canonical(name: &str) -> Option<Type> {
+        if let Some(&ty) = StandardTypes::get().aliases.get(name) {
+            return Some(ty);
+        }
+
+        Self::all().iter().copied().find(|ty| ty.short_name() == name)
+    }
+
+    /// All types of the standard library that have a constructor, together
+    /// with that constructor.
+    ///
+    /// Useful to offer the creation of any kind of value, e.g. in a form
+    /// generator.
+    pub fn constructible() -> impl Iterator<Item = (Type, Func)> {
+        Self::all().iter().filter_map(|&ty| Some((ty, ty.constructor_opt()?)))
+    }
+
+    /// The element function of a content value, i.e. the same function that
+    /// `content.func()` returns. Returns `None` for values that aren't
+    /// content.
+    pub fn element_func(value: &Value) -> Option<Func> {
+        match value {
+            Value::Content(content) => Some(content.func().into()),
+            _ => None,
+        }
+    }
+
     /// The type's associated scope that holds sub-definitions.
     pub fn scope(&self) -> &'static Scope {
         &(self.0).0.scope
     }
 
+    /// Looks up a function in the scope of `type` that can also be called as
+    /// a method on a type, with the type as the receiver. For instance,
+    /// `{str.fields()}` is the same as `{type.fields(str)}`.
+    ///
+    /// Only functions that describe the given type are methods. The other
+    /// definitions, like `type.all`, are only available on `type` itself.
+    pub fn method(name: &str) -> Option<&'static Binding> {
+        const METHODS: &[&str] = &[
+            "info",
+            "compatible",
+            "is-dict-key",
+            "fields",
+            "has-field",
+            "constructor",
+            "has-constructor",
+            "default",
+            "title",
+            "slug",
+            "docs",
+            "keywords",
+            "category",
+        ];
+        if !METHODS.contains(&name) {
+            return None;
+        }
+        Type::of::<Type>().scope().get(name)
+    }
+
+    /// Iterate over all definitions in this type's scope and, recursively, in
+    /// the scopes of the types, functions, and modules defined therein.
+    ///
+    /// Each definition is yielded with its path relative to this type, e.g.
+    /// `["cell"]` for `table.cell` or `["cell", "x"]` for something defined
+    /// in the scope of `cell`. Scopes that were already visited are skipped,
+    /// so cyclic definitions don't lead to infinite iteration.
+    pub fn iter_recursive(
+        &self,
+    ) -> impl Iterator<Item = (Vec<&'static str>, &'static Value)> {
+        let mut output = vec![];
+        let scope = self.scope();
+        walk_scope(scope, &mut vec![], &mut vec![scope], &mut output);
+        output.into_iter()
+    }
+
     /// Get a field from this type's scope, if possible.
+    ///
+    /// The scope is built once per type on first access and the lookup is a
+    /// hash map access, so this is cheap enough for hot method dispatch. The
+    /// `sink` is only used if the binding is deprecated.
     pub fn field(
         &self,
         field: &str,
         sink: impl DeprecationSink,
     ) -> StrResult<&'static Value> {
-        match self.scope().get(field) {
-            Some(binding) => Ok(binding.read_checked(sink)),
+        match self.field_checked(field, || sink) {
+            Some(value) => Ok(value),
             None => bail!("type {self} does not contain field `{field}`"),
         }
     }
+
+    /// Get a field from this type's scope, if possible, only creating the
+    /// deprecation sink if the binding is deprecated.
+    ///
+    /// This is the fast path for method dispatch: The sink typically borrows
+    /// the engine and almost no binding is deprecated. A missing field yields
+    /// `None` instead of building an error message.
+    pub fn field_checked<S: DeprecationSink>(
+        &self,
+        field: &str,
+        sink: impl FnOnce() -> S,
+    ) -> Option<&'static Value> {
+        let binding = self.scope().get(field)?;
+        Some(match binding.deprecation() {
+            None => binding.read(),
+            Some(_) => binding.read_checked(sink()),
+        })
+    }
+
+    /// The shortest sequence of types a value of type `from` passes through
+    /// when it is converted to `to` by repeatedly calling type constructors.
+    ///
+    /// The returned path starts with `from` and ends with `to`. Each step is a
+    /// conversion that the next type's constructor accepts directly (e.g.
+    /// `label` to `str` to `int`). Returns `None` if `to` cannot be reached.
+    pub fn coercion_path(from: Type, to: Type) -> Option<Vec<Type>> {
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        // Constructors only tell us which types they accept and not which
+        // ones they can produce, so we search backwards from the target. For
+        // each discovered type, we remember the type it converts into.
+        let mut next = FxHashMap::default();
+        let mut queue = VecDeque::from([to]);
+        while let Some(ty) = queue.pop_front() {
+            for source in ty.constructor_inputs() {
+                if source == to || next.contains_key(&source) {
+                    continue;
+                }
+
+                next.insert(source, ty);
+                if source == from {
+                    let mut path = vec![from];
+                    let mut current = from;
+                    while current != to {
+                        current = next[&current];
+                        path.push(current);
+                    }
+                    return Some(path);
+                }
+
+                queue.push_back(source);
+            }
+        }
+
+        None
+    }
+
+    /// Converts a value to this type by calling the type's constructor.
+    ///
+    /// Values that already are of this type are returned unchanged.
+    pub fn coerce_to(
+        &self,
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        span: Span,
+        value: Value,
+    ) -> SourceResult<Value> {
+        if value.ty() == *self {
+            return Ok(value);
+        }
+
+        let constructor = self.constructor().at(span)?;
+        constructor.spanned(span).call(engine, context, [value])
+    }
+
+    /// The types the constructor accepts for its first positional parameter.
+    ///
+    /// Parameters that accept any value are ignored, as they don't represent
+    /// a meaningful conversion.
+    fn constructor_inputs(&self) -> Vec<Type> {
+        let mut types = vec![];
+        let Ok(constructor) = self.constructor() else { return types };
+        let Some(param) = constructor
+            .params()
+            .and_then(|params| params.iter().find(|param| param.positional))
+        else {
+            return types;
+        };
+
+        param.input.walk(|info| {
+            let ty = match info {
+                CastInfo::Type(ty) => *ty,
+                CastInfo::Value(value, _) => value.ty(),
+                _ => return,
+            };
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        });
+
+        types
+    }
 }
 
 #[scope]
@@ -140,24 +667,397 @@ impl Type {
     ) -> Type {
         value.ty()
     }
+
+    /// Whether two values are equal when integers and floats are treated as
+    /// interchangeable.
+    ///
+    /// An integer and a float are equal if they have the same numeric value,
+    /// so `{1}` and `{1.0}` are loosely equal. Arrays and dictionaries are
+    /// loosely equal if they have the same length (and, for dictionaries, the
+    /// same keys) and their items are loosely equal. All other values are
+    /// compared with `==`.
+    ///
+    /// ```example
+    /// #type.loose-eq((1, 2.0), (1.0, 2)) \
+    /// #type.loose-eq((a: 1), (a: 1.5))
+    /// ```
+    #[func(title = "Loosely Equal")]
+    pub fn loose_eq(
+        /// The first value to compare.
+        a: Value,
+        /// The second value to compare.
+        b: Value,
+    ) -> bool {
+        a.structurally_equal_loose(&b)
+    }
+
+    /// Lists all types in the standard library.
+    ///
+    /// Each entry is a dictionary with the `type` itself and its `name`,
+    /// `title`, and `docs`, which is useful to build a reference page. The
+    /// entries are sorted by the types' long names.
+    ///
+    /// ```example
+    /// #set text(6pt)
+    /// #type.all().slice(0, 3).map(entry => entry.title).join(", ")
+    /// ```
+    #[func(name = "all")]
+    pub fn list_all() -> Array {
+        static LIST: LazyLock<Array> = LazyLock::new(|| {
+            let mut types = Type::all().to_vec();
+            types.sort();
+            types
+                .into_iter()
+                .map(|ty| {
+                    dict! {
+                        "type" => ty,
+                        "name" => ty.short_name(),
+                        "title" => ty.title(),
+                        "docs" => ty.docs(),
+                    }
+                    .into_value()
+                })
+                .collect()
+        });
+        LIST.clone()
+    }
+
+    /// Converts each value of an array to the given type.
+    ///
+    /// Each value is converted with the type's constructor, so this is a
+    /// batch version of, e.g., calling `int` on every value. Values that are
+    /// already of the target type are kept as is. This is useful to import
+    /// homogeneous data, like a column of a CSV file. If a value fails to
+    /// convert, the error names the index of the first failing value.
+    ///
+    /// ```example
+    /// #type.coerce-array(("1", "2", 3.5), int)
+    /// ```
+    #[func]
+    pub fn coerce_array(
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        span: Span,
+        /// The values to convert.
+        values: Array,
+        /// The type to convert the values to.
+        target: Type,
+    ) -> SourceResult<Array> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                target.coerce_to(engine, context, span, value).map_err(|errors| {
+                    errors
+                        .into_iter()
+                        .map(|mut error| {
+                            error.message = eco_format!(
+                                "failed to convert value at index {i}: {}",
+                                error.message
+                            );
+                            error
+                        })
+                        .collect()
+                })
+            })
+            .collect()
+    }
+
+    /// Whether a value would be accepted where a value of the given type is
+    /// expected, e.g. by a function parameter.
+    ///
+    /// This is broader than comparing the value's type with `==` because it
+    /// takes automatic conversions into account: An integer is accepted where
+    /// a float is expected, a length where a relative length is expected, and
+    /// a string where content is expected.
+    ///
+    /// ```example
+    /// #(type(1) == float) \
+    /// #type.compatible(float, 1) \
+    /// #type.compatible(int, 1.0)
+    /// ```
+    #[func]
+    pub fn compatible(
+        /// The expected type.
+        ty: Type,
+        /// The value to check.
+        value: Value,
+    ) -> bool {
+        ty.is_compatible(&value)
+    }
+
+    /// Whether values of the given type can be used as dictionary keys.
+    ///
+    /// Currently, only strings can be keys.
+    ///
+    /// ```example
+    /// #type.is-dict-key(str) \
+    /// #type.is-dict-key(int)
+    /// ```
+    #[func(title = "Is Dictionary Key")]
+    pub fn is_dict_key(
+        /// The type to check.
+        ty: Type,
+    ) -> bool {
+        ty.is_valid_dict_key()
+    }
+
+    /// The names of all fields defined on a type, such as its methods.
+    ///
+    /// Any of these names can be accessed on the type with the dot syntax.
+    /// Deprecated fields are omitted. Like the other functions of `type`, this
+    /// can be called as `{str.fields()}` or as `{type.fields(str)}`.
+    ///
+    /// ```example
+    /// #int.fields() \
+    /// #type.fields(int)
+    /// ```
+    #[func]
+    pub fn fields(
+        /// The type whose fields to list.
+        ty: Type,
+    ) -> Array {
+        field_names(ty.scope())
+    }
+
+    /// Whether a type defines a field with the given name, such as a method.
+    ///
+    /// This is useful to detect features that are only available in some
+    /// versions of Typst. Unlike [`type.fields`]($type.fields), this also
+    /// finds deprecated fields, since they can still be accessed.
+    ///
+    /// ```example
+    /// #type.has-field(int, "signum") \
+    /// #type.has-field(int, "nonexistent")
+    /// ```
+    #[func]
+    pub fn has_field(
+        /// The type to check.
+        ty: Type,
+        /// The name of the field.
+        name: Str,
+    ) -> bool {
+        defines_field(ty.scope(), &name)
+    }
+
+    /// The constructor function of a type.
+    ///
+    /// Calling a type directly is the same as calling its constructor, but
+    /// the constructor is a regular function and can thus be passed around
+    /// and, for instance, be [applied]($function.with) partially. Fails for
+    /// types without a constructor, like `content`. To check whether a type
+    /// has a constructor before retrieving it, use
+    /// [`type.has-constructor`]($type.has-constructor).
+    ///
+    /// ```example
+    /// #let parse = type.constructor(int)
+    /// #parse("42") \
+    /// #("1", "2").map(type.constructor(float))
+    /// ```
+    #[func(name = "constructor")]
+    pub fn constructor_of(
+        /// The type whose constructor to retrieve.
+        ty: Type,
+    ) -> StrResult<Func> {
+        ty.constructor()
+    }
+
+    /// Whether a type has a constructor function.
+    ///
+    /// If this is `{true}`, [`type.constructor`]($type.constructor) succeeds
+    /// for the type and the type can be called directly.
+    ///
+    /// ```example
+    /// #type.has-constructor(int) \
+    /// #type.has-constructor(content)
+    /// ```
+    #[func(name = "has-constructor")]
+    pub fn has_constructor_of(
+        /// The type to check.
+        ty: Type,
+    ) -> bool {
+        ty.has_constructor()
+    }
+
+    /// The empty or zero value of a type.
+    ///
+    /// This is useful to initialize a value generically, e.g. as the
+    /// starting point of a fold. Fails for types without a meaningful
+    /// default value, like `content` or `type`.
+    ///
+    /// ```example
+    /// #type.default(int) \
+    /// #type.default(length) \
+    /// #repr(type.default(str)) \
+    /// #repr(type.default(array))
+    /// ```
+    #[func(name = "default")]
+    pub fn default_of(
+        /// The type whose default value to retrieve.
+        ty: Type,
+    ) -> StrResult<Value> {
+        ty.default_value()
+    }
+
+    /// Looks up a type by its name.
+    ///
+    /// Both the short name used in code (e.g. `{"str"}`) and the long name
+    /// used in diagnostics (e.g. `{"string"}`) are accepted, as are
+    /// deprecated aliases. The types of `{none}` and `{auto}` are found
+    /// under the names `{"none"}` and `{"auto"}`. Fails if no type of the
+    /// standard library has the name.
+    ///
+    /// ```example
+    /// #type.from-name("length") \
+    /// #type.from-name("string") \
+    /// #type.from-name("none")
+    /// ```
+    #[func]
+    pub fn from_name(
+        /// The short or long name of the type.
+        name: Str,
+    ) -> StrResult<Type> {
+        let special = [Type::of::<NoneValue>(), Type::of::<AutoValue>()];
+        Type::canonical(&name)
+            .or_else(|| {
+                Self::all().iter().chain(&special).copied().find(|ty| {
+                    ty.short_name() == name.as_str() || ty.long_name() == name.as_str()
+                })
+            })
+            .ok_or_else(|| eco_format!("unknown type `{name}`"))
+    }
+
+    /// The title case name of a type, as used in the documentation.
+    ///
+    /// ```example
+    /// #type.title(str) \
+    /// #type.title(int)
+    /// ```
+    #[func(name = "title")]
+    pub fn title_of(
+        /// The type whose title to retrieve.
+        ty: Type,
+    ) -> Str {
+        ty.title().into()
+    }
+
+    /// A URL-safe identifier for a type, e.g. for anchors in a generated type
+    /// index.
+    ///
+    /// The slug is the lowercased short name of the type with all characters
+    /// other than ASCII letters and digits replaced by hyphens. Slugs are
+    /// unique across all types of the standard library.
+    ///
+    /// ```example
+    /// #type.slug(str) \
+    /// #type.slug(type(none))
+    /// ```
+    #[func(name = "slug")]
+    pub fn slug_of(
+        /// The type whose slug to retrieve.
+        ty: Type,
+    ) -> EcoString {
+        ty.slug()
+    }
+
+    /// The documentation of a type as a Markdown string.
+    ///
+    /// ```example
+    /// #type.docs(int).split("\n").first()
+    /// ```
+    #[func(name = "docs")]
+    pub fn docs_of(
+        /// The type whose documentation to retrieve.
+        ty: Type,
+    ) -> Str {
+        ty.docs().into()
+    }
+
+    /// The search keywords of a type, as used by the documentation.
+    ///
+    /// Types without keywords yield an empty array.
+    ///
+    /// ```example
+    /// #type.keywords(tiling) \
+    /// #type.keywords(float)
+    /// ```
+    #[func(name = "keywords")]
+    pub fn keywords_of(
+        /// The type whose keywords to retrieve.
+        ty: Type,
+    ) -> Array {
+        ty.keywords().iter().map(|&keyword| keyword.into_value()).collect()
+    }
+
+    /// The broad category of a type: `{"numeric"}`, `{"collection"}`,
+    /// `{"textual"}`, or `{"other"}`.
+    ///
+    /// Numbers and quantities like lengths are numeric, arrays and
+    /// dictionaries are collections, and strings and content are textual.
+    /// All remaining types fall back to `{"other"}`.
+    ///
+    /// ```example
+    /// #type.category(length) \
+    /// #type.category(dictionary) \
+    /// #type.category(color)
+    /// ```
+    #[func(name = "category")]
+    pub fn category_of(
+        /// The type whose category to retrieve.
+        ty: Type,
+    ) -> TypeCategory {
+        ty.category()
+    }
+
+    /// Bundles the metadata of a type into a dictionary.
+    ///
+    /// The dictionary contains the type's `name` as used in code, its `title`
+    /// and `long-name` as used in documentation and diagnostics, its search
+    /// `keywords`, whether it `has-constructor`, and a `docs-summary` with
+    /// the first sentence of its documentation.
+    ///
+    /// ```example
+    /// #let info = type.info(int)
+    /// #info.title: #info.docs-summary
+    /// ```
+    #[func]
+    pub fn info(
+        /// The type to describe.
+        ty: Type,
+    ) -> Dict {
+        dict! {
+            "name" => ty.short_name(),
+            "title" => ty.title(),
+            "long-name" => ty.long_name(),
+            "keywords" => ty.keywords().to_vec(),
+            "has-constructor" => ty.has_constructor(),
+            "docs-summary" => ty.docs_summary(),
+        }
+    }
 }
 
 impl Debug for Type {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Type({})", self.long_name())
+        write!(f, "Type({})", self.short_name())
     }
 }
 
+// The representation evaluates back to the same type: The types of `auto`
+// and `none` don't have a name, so they are written as a call to `type`.
+// All other types are written as the path under which they are defined.
 impl Repr for Type {
     fn repr(&self) -> EcoString {
         if *self == Type::of::<AutoValue>() {
-            "type(auto)"
+            "type(auto)".into()
         } else if *self == Type::of::<NoneValue>() {
-            "type(none)"
+            "type(none)".into()
         } else {
-            self.short_name()
+            StandardTypes::get()
+                .paths
+                .get(self)
+                .cloned()
+                .unwrap_or_else(|| self.short_name().into())
         }
-        .into()
     }
 }
 
@@ -167,6 +1067,23 @@ impl Display for Type {
     }
 }
 
+// Equality and hashing are based on the long name rather than on the address
+// of the type's data, so that they are stable across builds and consistent
+// with the ordering.
+impl Eq for Type {}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 || self.long_name() == other.long_name()
+    }
+}
+
+impl Hash for Type {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.long_name().hash(state);
+    }
+}
+
 impl Ord for Type {
     fn cmp(&self, other: &Self) -> Ordering {
         self.long_name().cmp(other.long_name())
@@ -179,6 +1096,203 @@ impl PartialOrd for Type {
     }
 }
 
+/// A type together with refinements that a value must satisfy, e.g. for
+/// validating form inputs.
+#[derive(Clone)]
+pub struct TypeConstraint {
+    ty: Type,
+    predicates: Vec<Predicate>,
+}
+
+/// A refinement of a [`TypeConstraint`].
+type Predicate = Arc<dyn Fn(&Value) -> StrResult<()> + Send + Sync>;
+
+impl TypeConstraint {
+    /// A constraint that accepts all values of the given type.
+    pub fn new(ty: Type) -> Self {
+        Self { ty, predicates: vec![] }
+    }
+
+    /// The base type of the constraint.
+    pub fn ty(&self) -> Type {
+        self.ty
+    }
+
+    /// Additionally requires values to satisfy the given predicate, which
+    /// returns an error message if they don't.
+    pub fn with<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Value) -> StrResult<()> + Send + Sync + 'static,
+    {
+        self.predicates.push(Arc::new(predicate));
+        self
+    }
+
+    /// Additionally requires values to be between `min` and `max`, inclusive.
+    ///
+    /// Values that can't be compared with the bounds are rejected.
+    pub fn range(self, min: Value, max: Value) -> Self {
+        let ty = self.ty;
+        self.with(move |value| {
+            let below = ops::compare(value, &min)? == Ordering::Less;
+            let above = ops::compare(value, &max)? == Ordering::Greater;
+            if below || above {
+                bail!(
+                    "expected {} between {} and {}, found {}",
+                    ty.long_name(),
+                    min.repr(),
+                    max.repr(),
+                    value.repr(),
+                );
+            }
+            Ok(())
+        })
+    }
+
+    /// Additionally requires values to be strings that fully match the given
+    /// regular expression.
+    pub fn pattern(self, regex: Regex) -> Self {
+        self.with(move |value| {
+            let Value::Str(string) = value else {
+                bail!("expected string, found {}", value.ty().long_name());
+            };
+            let full = regex
+                .find(string)
+                .is_some_and(|m| m.start() == 0 && m.end() == string.len());
+            if !full {
+                bail!(
+                    "expected string matching {}, found {}",
+                    regex.repr(),
+                    string.repr()
+                );
+            }
+            Ok(())
+        })
+    }
+
+    /// Checks whether the value fits the base type and all refinements.
+    pub fn check(&self, value: &Value) -> StrResult<()> {
+        if value.ty() != self.ty {
+            bail!("expected {}, found {}", self.ty.long_name(), value.ty().long_name());
+        }
+        self.predicates.iter().try_for_each(|predicate| predicate(value))
+    }
+}
+
+impl Debug for TypeConstraint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TypeConstraint")
+            .field("ty", &self.ty)
+            .field("predicates", &self.predicates.len())
+            .finish()
+    }
+}
+
+/// The expected shape of a possibly nested value, e.g. of a dictionary that
+/// holds form inputs.
+#[derive(Debug, Clone)]
+pub enum TypeSpec {
+    /// A value that satisfies a constraint.
+    Value(TypeConstraint),
+    /// An array whose items all match a spec.
+    Array(Box<TypeSpec>),
+    /// A dictionary with the given fields, each of which must be present and
+    /// match its spec. Additional fields are allowed.
+    Dict(Vec<(Str, TypeSpec)>),
+}
+
+impl TypeSpec {
+    /// Checks whether the value matches the spec, stopping at the first
+    /// mismatch.
+    pub fn check(&self, value: &Value) -> StrResult<()> {
+        match self.validate(value).into_iter().next() {
+            Some((path, message)) if path.is_empty() => Err(message),
+            Some((path, message)) => Err(eco_format!("{message} (at {path})")),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the value against the spec and collects all mismatches.
+    ///
+    /// Each mismatch comes with a JSON-pointer-like path to the offending
+    /// value, e.g. `/address/zip` or `/tags/2`. The value itself has the
+    /// empty path.
+    pub fn validate(&self, value: &Value) -> Vec<(EcoString, EcoString)> {
+        let mut errors = vec![];
+        self.validate_at(value, &mut EcoString::new(), &mut errors);
+        errors
+    }
+
+    /// Collects the mismatches for a value at the given path.
+    fn validate_at(
+        &self,
+        value: &Value,
+        path: &mut EcoString,
+        errors: &mut Vec<(EcoString, EcoString)>,
+    ) {
+        let len = path.len();
+        match (self, value) {
+            (Self::Value(constraint), _) => {
+                if let Err(message) = constraint.check(value) {
+                    errors.push((path.clone(), message));
+                }
+            }
+            (Self::Array(item), Value::Array(array)) => {
+                for (i, value) in array.iter().enumerate() {
+                    path.push_str(&eco_format!("/{i}"));
+                    item.validate_at(value, path, errors);
+                    path.truncate(len);
+                }
+            }
+            (Self::Dict(fields), Value::Dict(dict)) => {
+                for (key, spec) in fields {
+                    // Escape like a JSON pointer does.
+                    path.push('/');
+                    path.push_str(&key.as_str().replace('~', "~0").replace('/', "~1"));
+                    match dict.get(key) {
+                        Ok(value) => spec.validate_at(value, path, errors),
+                        Err(message) => errors.push((path.clone(), message)),
+                    }
+                    path.truncate(len);
+                }
+            }
+            (Self::Array(_), _) => errors.push((
+                path.clone(),
+                eco_format!("expected array, found {}", value.ty().long_name()),
+            )),
+            (Self::Dict(_), _) => errors.push((
+                path.clone(),
+                eco_format!("expected dictionary, found {}", value.ty().long_name()),
+            )),
+        }
+    }
+}
+
+/// How expensive it is to clone a value of some type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CloneCost {
+    /// The value is small and plain data, like an integer or a boolean.
+    Cheap,
+    /// The value is reference-counted, so cloning only bumps a counter. The
+    /// underlying data is copied lazily when a clone is modified.
+    Shared,
+    /// Cloning copies nested data of the value.
+    Deep,
+}
+
+/// The broad category of a type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum TypeCategory {
+    /// Numbers and quantities, like integers, lengths, or angles.
+    Numeric,
+    /// Containers of other values, i.e. arrays and dictionaries.
+    Collection,
+    /// Strings and content.
+    Textual,
+    /// All other types.
+    Other,
+}
+
 /// A Typst type that is defined by a native Rust type.
 pub trait NativeType {
     /// The type's name.
@@ -208,6 +1322,18 @@ pub struct NativeTypeData {
     pub docs: &'static str,
     /// A list of alternate search terms for this type.
     pub keywords: &'static [&'static str],
+    /// The operators that values of this type support.
+    pub operators: &'static [&'static str],
+    /// The unit suffix with which values of this type are displayed.
+    pub unit: Option<&'static str>,
+    /// How expensive it is to clone values of this type.
+    pub clone_cost: CloneCost,
+    /// The broad category of this type.
+    pub category: TypeCategory,
+    /// Produces the canonical empty or zero value of this type.
+    pub default: Option<fn() -> Value>,
+    /// Whether a value can be cast to this type.
+    pub castable: fn(&Value) -> bool,
     /// The constructor for this type.
     pub constructor: LazyLock<Option<&'static NativeFuncData>>,
     /// Definitions in the scope of the type.
@@ -224,3 +1350,424 @@ cast! {
     &'static NativeTypeData,
     self => Type::from(self).into_value(),
 }
+
+/// Test support for substituting type constructors.
+#[cfg(test)]
+mod testing {
+    use std::cell::RefCell;
+
+    use rustc_hash::FxHashMap;
+
+    use super::Type;
+    use crate::foundations::Func;
+
+    thread_local! {
+        /// Constructors installed via [`Type::with_constructor_override`].
+        static OVERRIDES: RefCell<FxHashMap<Type, Func>> = RefCell::default();
+    }
+
+    impl Type {
+        /// Replaces this type's constructor with a stub until the returned
+        /// guard is dropped. Only affects the current thread.
+        pub fn with_constructor_override(self, stub: Func) -> ConstructorOverride {
+            let previous = OVERRIDES.with_borrow_mut(|map| map.insert(self, stub));
+            ConstructorOverride { ty: self, previous }
+        }
+    }
+
+    /// Restores a type's previous constructor when dropped.
+    #[must_use]
+    pub struct ConstructorOverride {
+        ty: Type,
+        previous: Option<Func>,
+    }
+
+    impl Drop for ConstructorOverride {
+        fn drop(&mut self) {
+            OVERRIDES.with_borrow_mut(|map| match self.previous.take() {
+                Some(previous) => map.insert(self.ty, previous),
+                None => map.remove(&self.ty),
+            });
+        }
+    }
+
+    /// The stub constructor currently installed for the type, if any.
+    pub fn constructor_override(ty: Type) -> Option<Func> {
+        OVERRIDES.with_borrow(|map| map.get(&ty).cloned())
+    }
+}
+
+/// Whether a scope defines the given name, deprecated or not.
+fn defines_field(scope: &Scope, name: &str) -> bool {
+    scope.get(name).is_some()
+}
+
+/// The names of the definitions in a scope that aren't deprecated.
+fn field_names(scope: &Scope) -> Array {
+    scope
+        .iter()
+        .filter(|(_, binding)| binding.deprecation().is_none())
+        .map(|(name, _)| Str::from(name.clone()).into_value())
+        .collect()
+}
+
+/// The types of the standard library, collected in a single walk over its
+/// scope.
+struct StandardTypes {
+    /// All types in definition order, each listed once.
+    all: Vec<Type>,
+    /// The path under which each type is defined, e.g. `str`.
+    paths: FxHashMap<Type, EcoString>,
+    /// The global names that are deprecated aliases for types, together with
+    /// the types they refer to.
+    aliases: FxHashMap<EcoString, Type>,
+}
+
+impl StandardTypes {
+    /// The types of the standard library.
+    fn get() -> &'static Self {
+        static TYPES: LazyLock<StandardTypes> =
+            LazyLock::new(|| StandardTypes::collect(&crate::standard_scope()));
+        &TYPES
+    }
+
+    /// Collects the types defined in a scope and its nested scopes.
+    ///
+    /// If a type is reachable under multiple paths, the shortest one that ends
+    /// in the type's name wins, so that deprecated aliases are avoided.
+    fn collect(scope: &Scope) -> Self {
+        let aliases = scope
+            .iter()
+            .filter(|(_, binding)| binding.deprecation().is_some())
+            .filter_map(|(name, binding)| match binding.read() {
+                Value::Type(ty) if ty.short_name() != name.as_str() => {
+                    Some((name.clone(), *ty))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut output = vec![];
+        walk_scope(scope, &mut vec![], &mut vec![scope], &mut output);
+
+        let mut all = vec![];
+        let mut best = FxHashMap::<Type, (bool, Vec<&str>)>::default();
+        for (path, value) in output {
+            let Value::Type(ty) = value else { continue };
+            if !all.contains(ty) {
+                all.push(*ty);
+            }
+
+            let alias = path.last() != Some(&ty.short_name());
+            let better = best.get(ty).is_none_or(|(other_alias, other)| {
+                (alias, path.len()) < (*other_alias, other.len())
+            });
+            if better {
+                best.insert(*ty, (alias, path));
+            }
+        }
+
+        let paths = best
+            .into_iter()
+            .map(|(ty, (_, path))| (ty, path.join(".").into()))
+            .collect();
+
+        Self { all, paths, aliases }
+    }
+}
+
+/// Collects the path-qualified definitions of a scope and its nested scopes.
+fn walk_scope<'a>(
+    scope: &'a Scope,
+    path: &mut Vec<&'a str>,
+    visited: &mut Vec<&'a Scope>,
+    output: &mut Vec<(Vec<&'a str>, &'a Value)>,
+) {
+    for (name, binding) in scope.iter() {
+        path.push(name.as_str());
+        let value = binding.read();
+        output.push((path.clone(), value));
+        if let Some(inner) = value.scope()
+            && !visited.iter().any(|&seen| std::ptr::eq(seen, inner))
+        {
+            visited.push(inner);
+            walk_scope(inner, path, visited, output);
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::{
+        Content, Decimal, Deprecation, Element, Label, Module, NativeElement, array,
+    };
+    use crate::layout::{Fr, Rel};
+    use crate::model::HeadingElem;
+    use crate::visualize::{Stroke, Tiling};
+
+    #[test]
+    fn test_coercion_path() {
+        let int = Type::of::<i64>();
+        let float = Type::of::<f64>();
+        let str = Type::of::<Str>();
+        let label = Type::of::<Label>();
+        assert_eq!(Type::coercion_path(int, int), Some(vec![int]));
+        assert_eq!(Type::coercion_path(int, float), Some(vec![int, float]));
+        assert_eq!(Type::coercion_path(label, int), Some(vec![label, str, int]));
+        assert_eq!(Type::coercion_path(int, Type::of::<Content>()), None);
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", Type::of::<Str>()), "Type(str)");
+    }
+
+    #[test]
+    fn test_numeric_set() {
+        let set = Type::numeric_set();
+        for ty in [
+            Type::of::<i64>(),
+            Type::of::<f64>(),
+            Type::of::<Decimal>(),
+            Type::of::<Length>(),
+            Type::of::<Angle>(),
+            Type::of::<Ratio>(),
+            Type::of::<Rel>(),
+            Type::of::<Fr>(),
+        ] {
+            assert!(set.contains(&ty));
+            assert!(ty.is_numeric());
+        }
+
+        let str = Type::of::<Str>();
+        assert!(!set.contains(&str));
+        assert!(!str.is_numeric());
+        assert_eq!(set.len(), 8);
+    }
+
+    #[test]
+    fn test_constructor_override() {
+        let content = Type::of::<Content>();
+        let stub = Func::from(Element::of::<HeadingElem>());
+        assert!(content.constructor().is_err());
+        {
+            let _guard = content.with_constructor_override(stub.clone());
+            assert_eq!(content.constructor(), Ok(stub.clone()));
+
+            // Overriding an existing constructor restores the original.
+            let int = Type::of::<i64>();
+            let original = int.constructor().unwrap();
+            let inner = int.with_constructor_override(stub.clone());
+            assert_eq!(int.constructor(), Ok(stub));
+            drop(inner);
+            assert_eq!(int.constructor(), Ok(original));
+        }
+        assert!(content.constructor().is_err());
+    }
+
+    #[test]
+    fn test_constructible() {
+        let int = Type::of::<i64>();
+        let (_, constructor) = Type::constructible().find(|&(ty, _)| ty == int).unwrap();
+        assert_eq!(constructor.name(), Some("int"));
+        assert!(constructor.params().is_some());
+
+        let content = Type::of::<Content>();
+        assert!(Type::all().contains(&content));
+        assert!(Type::constructible().all(|(ty, _)| ty != content));
+    }
+
+    #[test]
+    fn test_signature_string() {
+        let signature = Type::of::<i64>().signature_string();
+        assert!(signature.starts_with("integer — "));
+        assert!(signature.contains("(value: "));
+        assert!(signature.contains("str"));
+
+        // Types without constructor have no parameter list.
+        let signature = Type::of::<Content>().signature_string();
+        assert!(signature.starts_with("content — "));
+        assert!(!signature.ends_with(')'));
+    }
+
+    #[test]
+    fn test_constructor_param_types() {
+        let params = Type::of::<i64>().constructor_param_types().unwrap();
+        let (name, types) = &params[0];
+        assert_eq!(*name, "value");
+        for ty in [
+            Type::of::<i64>(),
+            Type::of::<bool>(),
+            Type::of::<f64>(),
+            Type::of::<Decimal>(),
+            Type::of::<Str>(),
+        ] {
+            assert!(types.contains(&ty));
+        }
+
+        assert!(Type::of::<Content>().constructor_param_types().is_none());
+    }
+
+    #[test]
+    fn test_type_constraint() {
+        let digit =
+            TypeConstraint::new(Type::of::<i64>()).range(Value::Int(0), Value::Int(9));
+        assert!(digit.check(&Value::Int(5)).is_ok());
+        assert_eq!(
+            digit.check(&Value::Int(12)).unwrap_err(),
+            "expected integer between 0 and 9, found 12",
+        );
+        assert_eq!(
+            digit.check(&Value::Str("5".into())).unwrap_err(),
+            "expected integer, found string",
+        );
+
+        let word =
+            TypeConstraint::new(Type::of::<Str>()).pattern(Regex::new("[a-z]+").unwrap());
+        assert!(word.check(&Value::Str("hello".into())).is_ok());
+        assert!(word.check(&Value::Str("hello world".into())).is_err());
+    }
+
+    #[test]
+    fn test_type_spec_validate() {
+        let spec = TypeSpec::Dict(vec![
+            ("name".into(), TypeSpec::Value(TypeConstraint::new(Type::of::<Str>()))),
+            ("age".into(), TypeSpec::Value(TypeConstraint::new(Type::of::<i64>()))),
+            (
+                "tags".into(),
+                TypeSpec::Array(Box::new(TypeSpec::Value(TypeConstraint::new(
+                    Type::of::<Str>(),
+                )))),
+            ),
+        ]);
+
+        let value = Value::Dict(dict! {
+            "name" => "Alice",
+            "age" => "thirty",
+            "tags" => array!["a", 2],
+        });
+        assert_eq!(
+            spec.validate(&value),
+            [
+                ("/age".into(), "expected integer, found string".into()),
+                ("/tags/1".into(), "expected string, found integer".into()),
+            ],
+        );
+        assert_eq!(
+            spec.check(&value).unwrap_err(),
+            "expected integer, found string (at /age)",
+        );
+        assert_eq!(
+            spec.validate(&Value::Int(1)),
+            [("".into(), "expected dictionary, found integer".into())],
+        );
+    }
+
+    #[test]
+    fn test_field_names() {
+        let mut scope = Scope::new();
+        scope.define("new", 1);
+        scope.define("old", 2).deprecated(Deprecation::new());
+        assert_eq!(field_names(&scope), array!["new"]);
+    }
+
+    #[test]
+    fn test_field_checked() {
+        let mut created = false;
+        let len = Type::of::<Str>().field_checked("len", || created = true);
+        assert!(len.is_some());
+        assert!(!created);
+        assert!(Type::of::<Str>().field_checked("nope", || ()).is_none());
+    }
+
+    #[test]
+    fn test_defines_field() {
+        // Deprecated fields are found as well.
+        let mut scope = Scope::new();
+        scope.define("old", 1).deprecated(Deprecation::new());
+        assert!(defines_field(&scope, "old"));
+        assert!(!field_names(&scope).contains("old".into_value()));
+        assert!(!defines_field(&scope, "new"));
+    }
+
+    #[test]
+    fn test_example_values() {
+        let ints = Type::of::<i64>().example_values();
+        assert!(ints.len() > 1);
+        assert!(ints.iter().all(|value| value.ty() == Type::of::<i64>()));
+        assert!(Type::of::<Tiling>().example_values().is_empty());
+    }
+
+    #[test]
+    fn test_standard_types() {
+        let types = StandardTypes::get();
+        assert_eq!(types.paths.len(), types.all.len());
+        assert!(types.all.iter().all(|ty| types.paths.contains_key(ty)));
+        assert!(types.aliases.values().all(|ty| types.all.contains(ty)));
+    }
+
+    #[test]
+    fn test_deprecated_alias() {
+        let tiling = Type::of::<Tiling>();
+        assert!(Type::is_deprecated_alias("pattern"));
+        assert!(!Type::is_deprecated_alias("tiling"));
+        assert_eq!(Type::canonical("pattern"), Some(tiling));
+        assert_eq!(Type::canonical("tiling"), Some(tiling));
+        assert_eq!(Type::canonical("nope"), None);
+    }
+
+    #[test]
+    fn test_eq_and_hash_by_name() {
+        let a = Type::of::<Str>();
+        let b = Str::from("a").into_value().ty();
+        let c = Type::all().iter().copied().find(|ty| ty.long_name() == "string");
+        assert_eq!(a, b);
+        assert_eq!(Some(a), c);
+        assert_eq!(typst_utils::hash128(&a), typst_utils::hash128(&b));
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_ne!(a, Type::of::<Content>());
+    }
+
+    #[test]
+    fn test_clone_cost() {
+        assert_eq!(Type::of::<i64>().clone_cost(), CloneCost::Cheap);
+        assert_eq!(Type::of::<Array>().clone_cost(), CloneCost::Shared);
+        assert_eq!(Type::of::<Stroke>().clone_cost(), CloneCost::Deep);
+    }
+
+    #[test]
+    fn test_reflect() {
+        assert_eq!(Type::reflect::<Str>(), ("str", Type::of::<Str>()));
+        assert_eq!(Type::reflect::<Label>(), ("label", Type::of::<Label>()));
+    }
+
+    #[test]
+    fn test_element_func() {
+        let heading = HeadingElem::new(Content::empty()).pack();
+        assert_eq!(
+            Type::element_func(&Value::Content(heading)),
+            Some(Element::of::<HeadingElem>().into()),
+        );
+        assert_eq!(Type::element_func(&Value::Int(1)), None);
+    }
+
+    #[test]
+    fn test_iter_recursive() {
+        let paths: Vec<_> =
+            Type::of::<Str>().iter_recursive().map(|(path, _)| path).collect();
+        assert!(paths.contains(&vec!["from-unicode"]));
+
+        let mut inner = Scope::new();
+        inner.define("x", 1);
+        let mut outer = Scope::new();
+        outer.define("inner", Module::new("inner", inner));
+        outer.define("y", 2);
+
+        let mut output = vec![];
+        walk_scope(&outer, &mut vec![], &mut vec![&outer], &mut output);
+        let paths: Vec<_> = output.into_iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, [vec!["inner"], vec!["inner", "x"], vec!["y"]]);
+    }
+}
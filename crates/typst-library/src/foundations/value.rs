@@ -88,6 +88,119 @@ pub enum Value {
     Dyn(Dynamic),
 }
 
+/// The kind of a [`Value`], i.e. its enum variant without the payload.
+///
+/// Each kind is named like the variant of [`Value`] it stands for. This pairs
+/// with [`Type`]: All kinds but [`ValueKind::Dyn`] correspond to exactly one
+/// type. It is useful to match exhaustively over values without resorting to
+/// type names.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ValueKind {
+    None,
+    Auto,
+    Bool,
+    Int,
+    Float,
+    Length,
+    Angle,
+    Ratio,
+    Relative,
+    Fraction,
+    Color,
+    Gradient,
+    Tiling,
+    Symbol,
+    Version,
+    Str,
+    Bytes,
+    Label,
+    Datetime,
+    Decimal,
+    Duration,
+    Content,
+    Styles,
+    Array,
+    Dict,
+    Func,
+    Args,
+    Type,
+    Module,
+    Dyn,
+}
+
+impl ValueKind {
+    /// All kinds, in the order of the variants of [`Value`].
+    pub const ALL: &'static [Self] = &[
+        Self::None,
+        Self::Auto,
+        Self::Bool,
+        Self::Int,
+        Self::Float,
+        Self::Length,
+        Self::Angle,
+        Self::Ratio,
+        Self::Relative,
+        Self::Fraction,
+        Self::Color,
+        Self::Gradient,
+        Self::Tiling,
+        Self::Symbol,
+        Self::Version,
+        Self::Str,
+        Self::Bytes,
+        Self::Label,
+        Self::Datetime,
+        Self::Decimal,
+        Self::Duration,
+        Self::Content,
+        Self::Styles,
+        Self::Array,
+        Self::Dict,
+        Self::Func,
+        Self::Args,
+        Self::Type,
+        Self::Module,
+        Self::Dyn,
+    ];
+
+    /// The type of values of this kind. Returns `None` for dynamic values,
+    /// which can be of many different types.
+    pub fn ty(self) -> Option<Type> {
+        Some(match self {
+            Self::None => Type::of::<NoneValue>(),
+            Self::Auto => Type::of::<AutoValue>(),
+            Self::Bool => Type::of::<bool>(),
+            Self::Int => Type::of::<i64>(),
+            Self::Float => Type::of::<f64>(),
+            Self::Length => Type::of::<Length>(),
+            Self::Angle => Type::of::<Angle>(),
+            Self::Ratio => Type::of::<Ratio>(),
+            Self::Relative => Type::of::<Rel<Length>>(),
+            Self::Fraction => Type::of::<Fr>(),
+            Self::Color => Type::of::<Color>(),
+            Self::Gradient => Type::of::<Gradient>(),
+            Self::Tiling => Type::of::<Tiling>(),
+            Self::Symbol => Type::of::<Symbol>(),
+            Self::Version => Type::of::<Version>(),
+            Self::Str => Type::of::<Str>(),
+            Self::Bytes => Type::of::<Bytes>(),
+            Self::Label => Type::of::<Label>(),
+            Self::Datetime => Type::of::<Datetime>(),
+            Self::Decimal => Type::of::<Decimal>(),
+            Self::Duration => Type::of::<Duration>(),
+            Self::Content => Type::of::<Content>(),
+            Self::Styles => Type::of::<Styles>(),
+            Self::Array => Type::of::<Array>(),
+            Self::Dict => Type::of::<Dict>(),
+            Self::Func => Type::of::<Func>(),
+            Self::Args => Type::of::<Args>(),
+            Self::Type => Type::of::<Type>(),
+            Self::Module => Type::of::<Module>(),
+            Self::Dyn => return None,
+        })
+    }
+}
+
 impl Value {
     /// Create a new dynamic value.
     pub fn dynamic<T>(any: T) -> Self
@@ -149,6 +262,42 @@ impl Value {
         }
     }
 
+    /// The kind of the value, i.e. which variant of the enum it is.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Self::None => ValueKind::None,
+            Self::Auto => ValueKind::Auto,
+            Self::Bool(_) => ValueKind::Bool,
+            Self::Int(_) => ValueKind::Int,
+            Self::Float(_) => ValueKind::Float,
+            Self::Length(_) => ValueKind::Length,
+            Self::Angle(_) => ValueKind::Angle,
+            Self::Ratio(_) => ValueKind::Ratio,
+            Self::Relative(_) => ValueKind::Relative,
+            Self::Fraction(_) => ValueKind::Fraction,
+            Self::Color(_) => ValueKind::Color,
+            Self::Gradient(_) => ValueKind::Gradient,
+            Self::Tiling(_) => ValueKind::Tiling,
+            Self::Symbol(_) => ValueKind::Symbol,
+            Self::Version(_) => ValueKind::Version,
+            Self::Str(_) => ValueKind::Str,
+            Self::Bytes(_) => ValueKind::Bytes,
+            Self::Label(_) => ValueKind::Label,
+            Self::Datetime(_) => ValueKind::Datetime,
+            Self::Decimal(_) => ValueKind::Decimal,
+            Self::Duration(_) => ValueKind::Duration,
+            Self::Content(_) => ValueKind::Content,
+            Self::Styles(_) => ValueKind::Styles,
+            Self::Array(_) => ValueKind::Array,
+            Self::Dict(_) => ValueKind::Dict,
+            Self::Func(_) => ValueKind::Func,
+            Self::Args(_) => ValueKind::Args,
+            Self::Type(_) => ValueKind::Type,
+            Self::Module(_) => ValueKind::Module,
+            Self::Dyn(_) => ValueKind::Dyn,
+        }
+    }
+
     /// Try to cast the value into a specific type.
     pub fn cast<T: FromValue>(self) -> HintedStrResult<T> {
         T::from_value(self)
@@ -163,7 +312,10 @@ impl Value {
             Self::Version(version) => version.component(field).map(Self::Int),
             Self::Dict(dict) => dict.get(field).cloned(),
             Self::Content(content) => content.field_by_name(field),
-            Self::Type(ty) => ty.field(field, sink).cloned(),
+            Self::Type(ty) => ty
+                .field(field, sink)
+                .cloned()
+                .or_else(|err| ty.reflection_field(field).ok_or(err)),
             Self::Func(func) => func.field(field, sink).cloned(),
             Self::Module(module) => module.field(field, sink).cloned(),
             _ => fields::field(self, field),
@@ -208,6 +360,37 @@ impl Value {
         }
     }
 
+    /// Whether two values are structurally equal, treating integers and
+    /// floats as interchangeable.
+    ///
+    /// The rule is as follows:
+    /// - An integer and a float are equal if the integer converted to a float
+    ///   is equal to the float. Two floats are equal if they are numerically
+    ///   equal.
+    /// - Two arrays are equal if they have the same length and their items are
+    ///   pairwise loosely equal.
+    /// - Two dictionaries are equal if they have the same keys and the values
+    ///   for each key are loosely equal. The order of the keys is irrelevant.
+    /// - All other values are compared with normal equality.
+    pub fn structurally_equal_loose(&self, other: &Value) -> bool {
+        match (self, other) {
+            (&Self::Int(i), &Self::Float(f)) | (&Self::Float(f), &Self::Int(i)) => {
+                i as f64 == f
+            }
+            (Self::Array(a), Self::Array(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b.iter()).all(|(x, y)| x.structurally_equal_loose(y))
+            }
+            (Self::Dict(a), Self::Dict(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, x)| {
+                        b.get(key).is_ok_and(|y| x.structurally_equal_loose(y))
+                    })
+            }
+            _ => self == other,
+        }
+    }
+
     /// Attach a span to the value, if possible.
     pub fn spanned(self, span: Span) -> Self {
         match self {
@@ -748,4 +931,21 @@ mod tests {
         test(dict!["one" => 1], "(one: 1)");
         test(dict!["two" => false, "one" => 1], "(two: false, one: 1)");
     }
+
+    #[test]
+    fn test_kind_round_trip() {
+        for value in [
+            Value::None,
+            Value::Int(1),
+            Value::Str("hi".into()),
+            Value::Length(Length::zero()),
+            Value::Array(array![1, 2]),
+        ] {
+            let kind = value.kind();
+            assert_eq!(Type::from_kind(kind), Some(value.ty()));
+            assert_eq!(value.ty().kind(), Some(kind));
+        }
+
+        assert_eq!(Type::from_kind(ValueKind::Dyn), None);
+    }
 }
@@ -6,7 +6,7 @@ use std::iter::repeat;
 use ecow::{EcoString, EcoVec, eco_format};
 
 use crate::diag::{StrResult, bail};
-use crate::foundations::{Repr, cast, func, repr, scope, ty};
+use crate::foundations::{Repr, Type, cast, func, repr, scope, ty};
 
 /// A version with an arbitrary number of components.
 ///
@@ -22,7 +22,7 @@ use crate::foundations::{Repr, cast, func, repr, scope, ty};
 ///
 /// You can convert a version to an array of explicitly given components using
 /// the [`array`] constructor.
-#[ty(scope, cast)]
+#[ty(scope, cast, operators = ["<", "<=", ">", ">="])]
 #[derive(Debug, Default, Clone, Hash)]
 #[allow(clippy::derived_hash_with_manual_eq)]
 pub struct Version(EcoVec<u32>);
@@ -185,7 +185,7 @@ impl Display for Version {
 impl Repr for Version {
     fn repr(&self) -> EcoString {
         let parts: Vec<_> = self.0.iter().map(|v| eco_format!("{v}")).collect();
-        eco_format!("version{}", &repr::pretty_array_like(&parts, false))
+        repr::constructor_or_literal(Type::of::<Self>(), &parts)
     }
 }
 
@@ -21,7 +21,7 @@ use crate::model::Numbering;
 /// elements with an explicit label. As a result, you _can_ query for e.g.
 /// [`strong`] elements, but you will find only those that have an explicit
 /// label attached to them. This limitation will be resolved in the future.
-#[ty(scope)]
+#[ty(scope, clone_cost = "cheap")]
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Location(u128);
 
@@ -137,7 +137,7 @@ pub struct AlignElem {
 /// #left.x \
 /// #left.y (none)
 /// ```
-#[ty(scope)]
+#[ty(scope, clone_cost = "cheap")]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Alignment {
     H(HAlignment),
@@ -19,7 +19,15 @@ use crate::foundations::{Repr, func, repr, scope, ty};
 /// ```example
 /// #rotate(10deg)[Hello there!]
 /// ```
-#[ty(scope, cast)]
+#[ty(
+    scope,
+    cast,
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    unit = "deg",
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Angle(Scalar);
 
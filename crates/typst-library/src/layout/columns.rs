@@ -1,15 +1,26 @@
 use std::num::NonZeroUsize;
 
-use crate::foundations::{Content, elem};
-use crate::layout::{Length, Ratio, Rel};
+use typst_syntax::Spanned;
+use typst_utils::NonZeroExt;
 
-/// Separates a region into multiple equally sized columns.
+use crate::diag::{SourceResult, bail};
+use crate::engine::Engine;
+use crate::foundations::{
+    Args, Cast, Construct, Content, Dict, Func, OneOrMultiple, Packed, Smart, StyleChain,
+    cast, dict, elem, scope,
+};
+use crate::introspection::{Locatable, Locator};
+use crate::layout::{Em, Length, Ratio, Regions, Rel, Sizing};
+use crate::visualize::{Paint, Stroke};
+
+/// Separates a region into multiple columns.
 ///
 /// The `column` function lets you separate the interior of any container into
-/// multiple columns. It will currently not balance the height of the columns.
-/// Instead, the columns will take up the height of their container or the
-/// remaining height on the page. Support for balanced columns is planned for
-/// the future.
+/// multiple columns. The columns are equally wide unless their
+/// [`widths`]($columns.widths) are given. By default, the columns are filled
+/// one after the other, so the last column may end up shorter than the others.
+/// To give the columns roughly the same height, enable
+/// [`balance`]($columns.balance).
 ///
 /// # Page-level columns { #page-level }
 /// If you need to insert columns across your whole document, use the `{page}`
@@ -39,22 +50,922 @@ use crate::layout::{Length, Ratio, Rel};
 ///
 /// #lorem(40)
 /// ```
-#[elem]
+#[elem(scope)]
 pub struct ColumnsElem {
     /// The number of columns.
+    ///
+    /// When set to `{auto}`, the count is chosen such that each column holds
+    /// roughly 65 characters per line at the current [text size]($text.size).
+    /// The count is then at least one and at most six.
+    ///
+    /// When set to a length, there are as many columns as fit into the region
+    /// with at least that width each, but at least one. This is useful if the
+    /// width of the region isn't known ahead of time.
+    ///
+    /// In both cases, there is a single column if the region is infinitely
+    /// wide.
+    ///
+    /// When set to a dictionary with the keys `min`, `max`, and `per-item`,
+    /// the count depends on the amount of content: There is one column for
+    /// every `per-item` top-level items of the body, rounded up and clamped to
+    /// the range from `min` to `max`. Top-level items are the children of the
+    /// body other than spaces, paragraph breaks, and spacing. The `min` and
+    /// `per-item` keys are optional and default to one. This is useful for
+    /// galleries with a varying number of items.
+    ///
+    /// When set to an array of integers, the entries are the column counts
+    /// of successive regions, e.g. `{(1, 2)}` for a single column on the
+    /// first page and two columns on all following ones. The last entry
+    /// applies to all remaining regions. As with
+    /// [individual widths]($columns.widths), paragraphs are broken into lines
+    /// for the narrowest column of all entries. A [column break]($colbreak) in
+    /// the last column of a region continues in the first column of the next
+    /// region, regardless of how many columns it has. Individual
+    /// [gutters]($columns.gutters) only apply to regions with a matching
+    /// number of columns. A schedule can't be combined with individual
+    /// widths and has no effect in [masonry]($columns.masonry) layout, where
+    /// the first entry applies to all regions.
+    ///
+    /// ```example
+    /// #set page(width: 300pt)
+    /// #set text(6pt)
+    /// #columns(auto)[
+    ///   #lorem(60)
+    /// ]
+    /// #columns(80pt)[
+    ///   #lorem(60)
+    /// ]
+    /// #columns((max: 3, per-item: 2))[
+    ///   #lorem(10)
+    ///
+    ///   #lorem(10)
+    ///
+    ///   #lorem(10)
+    /// ]
+    /// ```
     #[positional]
-    #[default(NonZeroUsize::new(2).unwrap())]
-    pub count: NonZeroUsize,
+    #[default(Smart::Custom(ColumnCount::Fixed(NonZeroUsize::new(2).unwrap())))]
+    #[parse(
+        let count = args.find()?;
+        count.clone()
+    )]
+    pub count: Smart<ColumnCount>,
+
+    /// The widths of the individual columns.
+    ///
+    /// When given, there is one column per entry and the
+    /// [`count`]($columns.count) may be omitted. If it is given, it must match
+    /// the number of entries. Fixed and relative widths are subtracted from
+    /// the available space first, together with the gutters. The remaining
+    /// space is then distributed between the [fractional]($fraction) entries
+    /// in proportion to their fractions, with `{auto}` counting as `{1fr}`.
+    /// Fractional columns end up with zero width if their fractions add up to
+    /// zero or if the fixed widths already take up all of the space. In the
+    /// latter case, the columns overflow their container and a warning is
+    /// emitted. When empty, all columns are equally wide.
+    ///
+    /// Note that paragraphs are broken into lines for the width of the
+    /// narrowest column, so that their lines fit into every column. Blocks
+    /// adapt to the column they end up in. This has no effect in
+    /// [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(widths: (auto, 30%), rule: 0.5pt)[
+    ///   #lorem(20)
+    ///   #colbreak()
+    ///   #text(0.8em, lorem(8))
+    /// ]
+    /// ```
+    #[parse(parse_widths(args, count.as_ref())?)]
+    pub widths: Vec<Sizing>,
 
     /// The size of the gutter space between each column.
+    ///
+    /// Relative gutters are resolved relative to the width available to the
+    /// columns. For columns nested in other columns, this is the width of the
+    /// enclosing column, not the width of the page.
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// The sizes of the individual gaps between the columns.
+    ///
+    /// Entry `i` is the gap between column `i` and the next one, so there must
+    /// be one entry less than there are columns. With a
+    /// [count schedule]($columns.count), at least one entry of the schedule
+    /// must have a matching number of columns. `{auto}` entries use the
+    /// [`gutter`]($columns.gutter). When empty, all gaps are as wide as the
+    /// gutter. This has no effect in [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(3, gutters: (4pt, 16pt), rule: 0.5pt)[
+    ///   #lorem(30)
+    /// ]
+    /// ```
+    pub gutters: Vec<Smart<Rel<Length>>>,
+
+    /// The gutter between the columns in all regions but the first one.
+    ///
+    /// When `{auto}`, all regions use the regular [`gutter`]($columns.gutter).
+    /// When set, the column widths in continuation regions are computed with
+    /// this gutter instead. For instance, a gutter of `{0pt}` lets the
+    /// columns on continuation pages abut seamlessly.
+    ///
+    /// Note that paragraphs are broken into lines for the column width of the
+    /// first region, so their lines don't become wider in later regions.
+    pub gutter_continuation: Smart<Rel<Length>>,
+
+    /// The available height of each column, relative to the height of the
+    /// region.
+    ///
+    /// The first value applies to the first column, the second value to the
+    /// second column, and so on. Columns without a value take up the full
+    /// height. When content doesn't fit into a shorter column, it flows into
+    /// the next column as usual. Values are clamped to be between `{0%}` and
+    /// `{100%}`.
+    ///
+    /// This has no effect in [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #columns(2, column-heights: (100%, 60%))[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    pub column_heights: Vec<Ratio>,
+
+    /// How many columns receive content in each region.
+    ///
+    /// The remaining columns are left empty, e.g. to reserve space for other
+    /// content. They still take up their space and are considered for the
+    /// [column rules]($columns.rule). Content that doesn't fit into the
+    /// filled columns continues in the next region. When set to `{auto}`,
+    /// all columns are filled. Values larger than the number of columns have
+    /// the same effect.
+    ///
+    /// This has no effect in [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(3, fill-columns: 2, rule: 0.5pt)[
+    ///   #lorem(30)
+    /// ]
+    /// ```
+    pub fill_columns: Smart<NonZeroUsize>,
+
+    /// How to handle a block that is wider than all columns combined.
+    ///
+    /// - `{"overflow"}`: The block overflows its column, overlapping the
+    ///   neighbouring columns. A warning is emitted.
+    /// - `{"shrink"}`: The block is scaled down to the width of a column.
+    /// - `{"span"}`: The block is taken out of its column and placed at the
+    ///   top or bottom of the region, spanning all columns like a
+    ///   [parent-scoped float]($place.scope). If it is still wider than the
+    ///   columns, it is scaled down to their width.
+    ///
+    /// This has no effect in [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #columns(2, wide-child: "shrink")[
+    ///   #block(width: 300pt, height: 40pt, fill: aqua)
+    ///   #lorem(20)
+    /// ]
+    /// ```
+    pub wide_child: WideChild,
+
+    /// Where to put the extra vertical space in a column whose content is
+    /// shorter than the column.
+    ///
+    /// - `{"bottom"}`: The space is left at the bottom of the column.
+    /// - `{"top"}`: The content is pushed to the bottom of the column.
+    /// - `{"between"}`: The space is distributed evenly between the blocks
+    ///   and paragraphs of the column. Lines within a paragraph keep their
+    ///   spacing.
+    ///
+    /// This only has an effect if the columns expand vertically, e.g. because
+    /// they fill a page or a block with a fixed height, and if the column
+    /// contains no [fractional spacing]($v). It has no effect in
+    /// [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #block(height: 100pt, columns(2, space-distribution: "between")[
+    ///   #rect(height: 20pt, width: 100%)
+    ///   #rect(height: 20pt, width: 100%)
+    ///   #rect(height: 20pt, width: 100%)
+    /// ])
+    /// ```
+    pub space_distribution: SpaceDistribution,
+
+    /// Whether to prefer column breaks at the end of a sentence.
+    ///
+    /// When a column would break within a sentence, but an earlier line of
+    /// the same column ends a sentence, Typst breaks after that line instead
+    /// if this leaves at most 20pt of the column empty. A line ends a
+    /// sentence if its text ends with a period, an exclamation mark, a
+    /// question mark, or an ellipsis, possibly followed by closing quotes or
+    /// brackets. Breaks that would leave a widow or orphan are not
+    /// considered.
+    ///
+    /// This is experimental. The detection of sentence boundaries is
+    /// heuristic and may, for instance, treat abbreviations as the end of a
+    /// sentence. It has no effect in [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    /// #columns(2, prefer-sentence-breaks: true)[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub prefer_sentence_breaks: bool,
+
+    /// The penalty for a column break that leaves the last line of a
+    /// paragraph alone at the top of the next column.
+    ///
+    /// Like a [column penalty]($colpenalty), this assigns a cost to a break
+    /// opportunity: When a column overflows, Typst compares the natural break
+    /// point with the earlier break opportunities in the column and breaks at
+    /// the cheapest one. The cost of an opportunity is its penalty plus the
+    /// space (in points) that breaking there leaves empty. While any of the
+    /// [widow]($columns.widow-penalty), [orphan]($columns.orphan-penalty),
+    /// and [column break]($columns.colbreak-penalty) penalties is non-zero,
+    /// the opportunities include the breaks between the lines of a paragraph
+    /// and after a paragraph. The latter cost nothing except the empty space.
+    /// With all penalties at zero, each column is simply filled as far as
+    /// possible.
+    ///
+    /// Note that widows and orphans are already prevented by the [text
+    /// costs]($text.costs) unless those are set to `{0%}`. The penalties
+    /// make such breaks costly instead of ruling them out. The choice is
+    /// made separately for each column. The penalties have no effect in
+    /// [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #set page(height: 70pt)
+    /// #set text(costs: (widow: 0%, orphan: 0%))
+    /// #columns(2, widow-penalty: 100)[
+    ///   #lorem(30)
+    /// ]
+    /// ```
+    #[default(0)]
+    pub widow_penalty: i64,
+
+    /// The penalty for a column break that leaves the first line of a
+    /// paragraph alone at the bottom of a column.
+    ///
+    /// See [`widow-penalty`]($columns.widow-penalty) for how the penalties
+    /// are taken into account.
+    #[default(0)]
+    pub orphan_penalty: i64,
+
+    /// The penalty for any column break between two lines of a paragraph.
+    ///
+    /// A positive penalty makes breaks between paragraphs more attractive. It
+    /// adds to the widow and orphan penalties. See
+    /// [`widow-penalty`]($columns.widow-penalty) for how the penalties are
+    /// taken into account.
+    #[default(0)]
+    pub colbreak_penalty: i64,
+
+    /// Whether to arrange the children in masonry style.
+    ///
+    /// Instead of filling one column after the other, each top-level child of
+    /// the body (a block or a paragraph) is placed at the bottom of whichever
+    /// column is currently the shortest. Consecutive children in the same
+    /// column are separated by the [paragraph spacing]($par.spacing).
+    ///
+    /// Note that this breaks the linear reading order: Content that comes
+    /// later in the source may end up above earlier content in a neighbouring
+    /// column. Column breaks have no effect in this mode.
+    ///
+    /// ```example
+    /// #set par(spacing: 4pt)
+    /// #columns(3, gutter: 4pt, masonry: true, {
+    ///   for (i, h) in (30pt, 12pt, 20pt, 8pt, 16pt).enumerate() {
+    ///     block(width: 100%, height: h, fill: aqua)[#(i + 1)]
+    ///   }
+    /// })
+    /// ```
+    #[default(false)]
+    pub masonry: bool,
+
+    /// The order in which the columns are filled.
+    ///
+    /// By default, one column is filled after the other. When set to
+    /// `{"row"}`, the top-level children of the body (blocks and paragraphs)
+    /// are instead placed left to right across a row, one per column, and
+    /// the next row starts below the tallest child of the previous one. In
+    /// right-to-left text, the rows are filled from right to left. This is
+    /// useful for calendar- or table-like layouts. Like in
+    /// [masonry]($columns.masonry) layout, consecutive rows are separated by
+    /// the [paragraph spacing]($par.spacing) and column breaks have no
+    /// effect. If both are enabled, the row order takes precedence.
+    ///
+    /// ```example
+    /// #set par(spacing: 4pt)
+    /// #columns(3, gutter: 4pt, fill-order: "row", {
+    ///   for (i, h) in (30pt, 12pt, 20pt, 8pt, 16pt).enumerate() {
+    ///     block(width: 100%, height: h, fill: aqua)[#(i + 1)]
+    ///   }
+    /// })
+    /// ```
+    pub fill_order: FillOrder,
+
+    /// Whether to balance the heights of the columns in the last region.
+    ///
+    /// Instead of filling the columns in the last region one after the
+    /// other, the content is distributed such that all columns end at roughly
+    /// the same height. To this end, the columns are laid out repeatedly with
+    /// decreasing heights, which makes this more expensive than the default
+    /// layout.
+    ///
+    /// This has no effect if the columns expand vertically, e.g. in a block
+    /// with a fixed height, or in [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #columns(2, balance: true)[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
+    /// Whether to align the bottoms of the columns in each region.
+    ///
+    /// The shorter columns are stretched to the height of the tallest one by
+    /// distributing the extra space between their blocks and paragraphs, as
+    /// with a [space distribution]($columns.space-distribution) of
+    /// `{"between"}`. A column without any such gap, e.g. one that holds just
+    /// a single unbreakable block, stays aligned to the top. This also
+    /// applies to the last column, so it is best combined with
+    /// [`balance`]($columns.balance).
+    ///
+    /// This only has an effect if the columns don't expand vertically. In
+    /// that case, use the space distribution instead. It has no effect in
+    /// [masonry]($columns.masonry) layout.
+    ///
+    /// ```example
+    /// #columns(2, bottom-align: true)[
+    ///   #rect(width: 100%, height: 30pt)
+    ///   #rect(width: 100%, height: 30pt)
+    ///   #colbreak()
+    ///   #rect(width: 100%, height: 80pt)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub bottom_align: bool,
+
+    /// Whether to avoid a final region that holds only a tiny bit of content.
+    ///
+    /// When the columns spill just barely into one more region (e.g. a few
+    /// lines onto the next page), Typst retries the layout with the automatic
+    /// spacing between blocks, paragraphs, and lines shrunk by up to 20%. If
+    /// the shrunk layout needs one region less, it is used. Otherwise, the
+    /// columns are laid out normally. The amount of content that can be
+    /// absorbed this way thus depends on how much spacing there is in the
+    /// previous regions. Explicit [vertical spacing]($v) is never shrunk.
+    ///
+    /// This has no effect in [masonry mode]($columns.masonry).
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(2, avoid-single-region: true)[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub avoid_single_region: bool,
+
+    /// How far the columns may overfill a region to avoid spilling into one
+    /// more region.
+    ///
+    /// When the columns spill into one more region, Typst retries the layout
+    /// with the region before the spill enlarged by this amount. If the
+    /// content then fits, the columns overfill that region, e.g. into the
+    /// bottom margin of a page, instead of continuing in a nearly empty
+    /// region. If the content exceeds the slack, it spills into the next
+    /// region as usual.
+    ///
+    /// This has no effect in [masonry mode]($columns.masonry).
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(2, overfill-slack: 1em)[
+    ///   #lorem(44)
+    /// ]
+    /// ```
+    pub overfill_slack: Length,
+
+    /// The minimum height of the columns in the last region.
+    ///
+    /// When the content barely spills into one more region, the last region
+    /// may end up with a single short column, e.g. one line of text. If the
+    /// columns in the last region would end up shorter than this height,
+    /// Typst pulls content over from the previous region by laying out the
+    /// columns again with that region slightly shortened. Relative lengths
+    /// are resolved relative to the height of the region.
+    ///
+    /// If [`balance`]($columns.balance) is enabled as well, balancing wins and
+    /// the minimum height is ignored. This has no effect if the columns
+    /// expand vertically or in [masonry mode]($columns.masonry).
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(2, min-height: 30%)[
+    ///   #lorem(46)
+    /// ]
+    /// ```
+    pub min_height: Rel<Length>,
+
+    /// Whether columns shrink to the width of their content.
+    ///
+    /// By default, each column takes up its full width. When shrink-wrapped,
+    /// a column is only as wide as its widest line or block, and the next
+    /// column follows right after it and the gutter. This is useful if the
+    /// columns hold narrow content, like small figures. Column
+    /// [rules]($columns.rule) are still drawn at the regular positions of the
+    /// gutters. This has no effect in [masonry]($columns.masonry) layout.
+    ///
+    /// This can also be set for [page-level columns]($page.columns) with a
+    /// `{set columns(shrink-wrap: true)}` rule.
+    ///
+    /// ```example
+    /// #columns(3, shrink-wrap: true)[
+    ///   #rect(width: 30pt)
+    ///   #colbreak()
+    ///   #rect(width: 30pt)
+    ///   #colbreak()
+    ///   #rect(width: 30pt)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub shrink_wrap: bool,
+
+    /// Whether to drop empty lines at the start and end of each column.
+    ///
+    /// A line counts as empty if it has no content at all, e.g. a line that
+    /// only consists of a [line break]($linebreak). Such lines waste space at
+    /// the top and bottom of a column. Lines at the very end of the columns
+    /// are kept.
+    ///
+    /// This can also be set for [page-level columns]($page.columns) with a
+    /// `{set columns(trim: true)}` rule.
+    ///
+    /// ```example
+    /// #set page(height: 60pt)
+    /// #columns(2, trim: true)[
+    ///   First line \
+    ///   Second line \
+    ///   \ \ \
+    ///   After the blank lines
+    /// ]
+    /// ```
+    #[default(false)]
+    pub trim: bool,
+
+    /// Whether to pick justification and hyphenation automatically based on
+    /// the width of the columns.
+    ///
+    /// Narrow columns (less than 20em wide) are set ragged with hyphenation
+    /// enabled, which avoids the large gaps between words that justification
+    /// produces at small line widths. Wider columns are
+    /// [justified]($par.justify) and hyphenated as [usual]($text.hyphenate).
+    ///
+    /// The choice overrides any `par.justify` and `text.hyphenate` settings
+    /// for the body of the columns.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #columns(2, typographic-auto: true)[
+    ///   #lorem(30)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub typographic_auto: bool,
+
+    /// How to fill the background of the columns.
+    ///
+    /// Can be a single paint for all columns or an array of paints, one per
+    /// column. An array is repeated if there are more columns than paints and
+    /// a `{none}` entry leaves the respective column unfilled. The fill spans
+    /// the column's width and extends down to the tallest column in each
+    /// region. It does not cover the gutters.
+    ///
+    /// ```example
+    /// #columns(2, fill: (luma(230), none))[
+    ///   #lorem(20)
+    /// ]
+    /// ```
+    pub fill: OneOrMultiple<Option<Paint>>,
+
+    /// A [stroke] for vertical rules in the gutters between the columns.
+    ///
+    /// ```example
+    /// #columns(2, rule: 0.5pt + gray)[
+    ///   #lorem(20)
+    /// ]
+    /// ```
+    #[fold]
+    pub rule: Option<Stroke>,
+
+    /// How far the [column rule]($columns.rule) extends vertically.
+    ///
+    /// - `{"content"}`: The rule ends with the tallest column in each region.
+    /// - `{"region"}`: The rule spans the full height of each region, even
+    ///   when the columns are shorter. To make room for it, the columns then
+    ///   take up the full height, too.
+    ///
+    /// When the columns are in a container with a fixed height, both options
+    /// behave the same. When the region has an infinite height, `{"region"}`
+    /// falls back to `{"content"}`.
+    pub rule_extend: ColumnRuleExtent,
+
+    /// The height of a fade-out at the bottom of the columns in the final
+    /// region.
+    ///
+    /// This indicates that the content is truncated, e.g. for a "read more"
+    /// preview of an article. The fade is drawn on top of the content as a
+    /// gradient into the [page's fill]($page.fill), or white if the page has
+    /// no solid fill. It spans the whole width of the columns and at most
+    /// their height. It doesn't affect the layout.
+    ///
+    /// When the columns expand vertically, e.g. in a [block] with a fixed
+    /// height, the fade is at the bottom of the region and not at the bottom
+    /// of the content.
+    ///
+    /// ```example
+    /// #columns(2, fade-bottom: 2em)[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    pub fade_bottom: Option<Length>,
+
+    /// Whether to overlay the columns and gutters with translucent
+    /// rectangles labelled with their widths.
+    ///
+    /// This is meant for debugging layout issues, e.g. with the gutter or the
+    /// column widths. The overlay doesn't affect the layout.
+    ///
+    /// ```example
+    /// #columns(2, debug: true)[
+    ///   #lorem(16)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub debug: bool,
+
+    /// Whether to emit a [marker]($columns.marker) for each column.
+    ///
+    /// The marker records the column's number and size. Its location is the
+    /// top-left corner of the column, so that tools can recover the boxes of
+    /// the columns in each region, e.g. for tagging or reflowing the output.
+    /// The markers don't affect the layout.
+    ///
+    /// This can also be set for [page-level columns]($page.columns) with a
+    /// `{set columns(markers: true)}` rule. No markers are emitted for a
+    /// single column or in [masonry mode]($columns.masonry).
+    ///
+    /// ```example
+    /// #columns(2, markers: true)[
+    ///   #lorem(20)
+    /// ]
+    /// #context query(columns.marker).map(it => it.column)
+    /// ```
+    #[default(false)]
+    pub markers: bool,
+
+    /// A [stroke] for horizontal rules at the top and bottom of the columns.
+    ///
+    /// The rules are drawn in every region the columns span, e.g. on every
+    /// page. To keep them at the same positions in each region, every region
+    /// except the last one is filled up to its full height. The last region
+    /// ends where its content ends, unless the columns are in a container
+    /// with a fixed height, in which case it is filled up as well.
+    ///
+    /// ```example
+    /// #columns(2, edge-rules: 0.5pt + gray)[
+    ///   #lorem(20)
+    /// ]
+    /// ```
+    #[fold]
+    pub edge_rules: Option<Stroke>,
+
+    /// A [stroke] for a horizontal rule at the bottom of the columns.
+    ///
+    /// The rule spans all columns and the gutters between them and is drawn
+    /// in every region the columns span. Unlike the
+    /// [edge rules]($columns.edge-rules), it doesn't fill up any region: It
+    /// sits where the tallest column in the region ends or, if the columns
+    /// expand vertically (e.g. in a [block] with a fixed height), at the
+    /// bottom of the region. Combine it with a [column rule]($columns.rule)
+    /// to also separate the columns.
+    ///
+    /// ```example
+    /// #columns(2, bottom-rule: 0.5pt)[
+    ///   #lorem(20)
+    /// ]
+    /// ```
+    #[fold]
+    pub bottom_rule: Option<Stroke>,
+
+    /// The spacing between the columns and the content before them.
+    ///
+    /// If set to `{none}`, the spacing is determined by the ambient
+    /// [block spacing]($block.above). Otherwise, this takes precedence over
+    /// the ambient spacing, just like setting the `above` property of a block
+    /// explicitly. Like block spacing, the gap collapses with adjacent
+    /// spacing: Of the gap and the spacing after the preceding block, the
+    /// explicitly set one wins over automatic paragraph spacing. If both are
+    /// explicitly set, the larger one is used. Weak [vertical spacing]($v)
+    /// takes precedence over both.
+    ///
+    /// ```example
+    /// Before
+    /// #columns(2, gap-before: 16pt, gap-after: 4pt)[
+    ///   A #colbreak() B
+    /// ]
+    /// After
+    /// ```
+    pub gap_before: Option<Length>,
+
+    /// The spacing between the columns and the content after them.
+    ///
+    /// Behaves just like [`gap-before`]($columns.gap-before), but for the
+    /// spacing below the columns.
+    pub gap_after: Option<Length>,
+
+    /// The distance between the lines of a baseline grid that the columns
+    /// snap to.
+    ///
+    /// If a column starts with a line of text, the line is moved down until
+    /// its baseline lies on a line of the grid. This way, the first lines of
+    /// the columns line up with each other and with surrounding text that is
+    /// set on the same grid, even if they differ in size. Columns that start
+    /// with a block are left as is.
+    ///
+    /// This can also be set for [page-level columns]($page.columns) with a
+    /// `{set columns(baseline-grid: ..)}` rule. It has no effect in
+    /// [masonry mode]($columns.masonry).
+    ///
+    /// ```example
+    /// #columns(2, baseline-grid: 12pt)[
+    ///   #text(14pt)[Large] #colbreak()
+    ///   Small
+    /// ]
+    /// ```
+    pub baseline_grid: Option<Length>,
+
+    /// The offset of a line of the [baseline grid]($columns.baseline-grid)
+    /// from the top of the columns.
+    ///
+    /// The grid has lines at this offset and at all multiples of its pitch
+    /// above and below it. In each region, the offset is measured from the
+    /// top of the columns in that region.
+    ///
+    /// To align the columns with a grid that is defined for the whole page,
+    /// subtract the position of the columns from the page grid's offset, e.g.
+    /// with [`here`] in a [context] expression:
+    ///
+    /// ```example
+    /// #v(7pt)
+    /// #context columns(
+    ///   2,
+    ///   baseline-grid: 12pt,
+    ///   grid-origin: 10pt - here().position().y,
+    /// )[
+    ///   #lorem(12)
+    /// ]
+    /// ```
+    pub grid_origin: Length,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
 }
 
+/// How far a column rule extends vertically.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ColumnRuleExtent {
+    /// The rule ends with the tallest column.
+    #[default]
+    Content,
+    /// The rule spans the full height of the region.
+    Region,
+}
+
+/// The number of columns.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum ColumnCount {
+    /// A fixed number of columns.
+    Fixed(NonZeroUsize),
+    /// As many columns as fit with at least the given width each.
+    MinWidth(Length),
+    /// One column per `per_item` top-level items, between `min` and `max`.
+    PerItem { min: NonZeroUsize, max: NonZeroUsize, per_item: NonZeroUsize },
+    /// The number of columns in successive regions. The last entry applies
+    /// to all remaining regions.
+    Schedule(Vec<NonZeroUsize>),
+}
+
+impl ColumnCount {
+    /// The number of columns for the given number of top-level items, if the
+    /// count depends on it.
+    pub fn for_items(&self, items: usize) -> Option<NonZeroUsize> {
+        let Self::PerItem { min, max, per_item } = self else { return None };
+        let count = items.div_ceil(per_item.get()).clamp(min.get(), max.get());
+        NonZeroUsize::new(count)
+    }
+}
+
+cast! {
+    ColumnCount,
+    self => match self {
+        Self::Fixed(count) => count.into_value(),
+        Self::MinWidth(width) => width.into_value(),
+        Self::PerItem { min, max, per_item } => dict! {
+            "min" => min,
+            "max" => max,
+            "per-item" => per_item,
+        }.into_value(),
+        Self::Schedule(counts) => counts.into_value(),
+    },
+    v: NonZeroUsize => Self::Fixed(v),
+    v: Length => Self::MinWidth(v),
+    v: Vec<NonZeroUsize> => {
+        if v.is_empty() {
+            bail!("column count schedule must not be empty");
+        }
+        Self::Schedule(v)
+    },
+    mut v: Dict => {
+        let mut take = |key| v.take(key).ok().map(|v| v.cast()).transpose();
+        let min = take("min")?.unwrap_or(NonZeroUsize::ONE);
+        let per_item = take("per-item")?.unwrap_or(NonZeroUsize::ONE);
+        let max = v.take("max")?.cast()?;
+        v.finish(&["min", "max", "per-item"])?;
+        if min > max {
+            bail!("minimum column count must not exceed the maximum");
+        }
+        Self::PerItem { min, max, per_item }
+    },
+}
+
+/// Parses the individual column widths and checks that they match a count
+/// given alongside them.
+///
+/// This can't be checked during layout because the count is always set by
+/// then, if only to its default.
+fn parse_widths(
+    args: &mut Args,
+    count: Option<&Smart<ColumnCount>>,
+) -> SourceResult<Option<Vec<Sizing>>> {
+    let Some(widths) = args.named::<Spanned<Vec<Sizing>>>("widths")? else {
+        return Ok(None);
+    };
+    if let Some(Smart::Custom(ColumnCount::Fixed(count))) = count
+        && !widths.v.is_empty()
+        && count.get() != widths.v.len()
+    {
+        bail!(
+            widths.span,
+            "column count ({count}) does not match the number of widths ({})",
+            widths.v.len();
+            hint: "the count can be omitted when widths are given",
+        );
+    }
+    Ok(Some(widths.v))
+}
+
+/// The order in which columns are filled.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum FillOrder {
+    /// Fill one column after the other.
+    #[default]
+    Column,
+    /// Fill the columns row by row, one child per column.
+    Row,
+}
+
+/// How to handle a block that is wider than all columns combined.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum WideChild {
+    /// The block overflows its column.
+    #[default]
+    Overflow,
+    /// The block is scaled down to the width of a column.
+    Shrink,
+    /// The block spans all columns.
+    Span,
+}
+
+/// Where to put the extra vertical space in a column.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum SpaceDistribution {
+    /// The space is left at the bottom of the column.
+    #[default]
+    Bottom,
+    /// The space is put above the content of the column.
+    Top,
+    /// The space is distributed evenly between the blocks of the column.
+    Between,
+}
+
+impl Packed<ColumnsElem> {
+    /// Determines the break opportunities in the columns' body, e.g. for
+    /// custom pagination.
+    ///
+    /// Returns the indices of the in-flow items before which a column break
+    /// can occur. The in-flow items are the lines of paragraphs and the
+    /// blocks, in order. Breaks within breakable blocks are not included.
+    pub fn break_candidates(
+        &self,
+        engine: &mut Engine,
+        locator: Locator,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Vec<usize>> {
+        (engine.routines.column_break_candidates)(self, engine, locator, styles, regions)
+    }
+}
+
+#[scope]
+impl ColumnsElem {
+    #[elem]
+    type ColumnsPin;
+
+    #[elem]
+    type ColumnsMarker;
+}
+
+/// Pins content to the top of a specific column.
+///
+/// Pinned content is taken out of the normal flow and placed at the top of
+/// the given column in the first region of the columns (e.g. on the first
+/// page). The remaining content then flows around it, starting in the first
+/// column as usual. This is useful for a fixed introduction in one column
+/// while the rest of the text flows through all of them.
+///
+/// When multiple pins target the same column, they are stacked in the order
+/// in which they appear in the source. If the column doesn't exist, the
+/// content is pinned to the last column.
+///
+/// ```example
+/// #columns(2)[
+///   #columns.pin(column: 2)[*Note:* Pinned to the second column.]
+///   #lorem(30)
+/// ]
+/// ```
+#[elem(name = "pin", title = "Column Pin")]
+pub struct ColumnsPin {
+    /// The column to pin the content to, starting at 1.
+    #[default(NonZeroUsize::new(1).unwrap())]
+    pub column: NonZeroUsize,
+
+    /// The spacing between the pinned content and the content below it.
+    #[default(Em::new(1.5).into())]
+    pub clearance: Length,
+
+    /// The content to pin.
+    #[required]
+    pub body: Content,
+}
+
+/// Marks the box of a column in a region.
+///
+/// Markers are emitted by columns with [`markers`]($columns.markers) enabled
+/// and can be [queried]($query). The [location]($location.position) of a
+/// marker is the top-left corner of its column.
+///
+/// ```example
+/// #columns(2, markers: true)[
+///   #lorem(20)
+/// ]
+/// #context for marker in query(columns.marker) [
+///   Column #marker.column: #marker.width wide \
+/// ]
+/// ```
+#[elem(name = "marker", title = "Column Marker", Locatable, Construct)]
+pub struct ColumnsMarker {
+    /// The number of the column within its region, starting at 1.
+    #[required]
+    pub column: NonZeroUsize,
+
+    /// The width of the column.
+    #[required]
+    pub width: Length,
+
+    /// The height of the column.
+    #[required]
+    pub height: Length,
+}
+
+impl Construct for ColumnsMarker {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
 /// Forces a column break.
 ///
 /// The function will behave like a [page break]($pagebreak) when used in a
@@ -78,10 +989,145 @@ pub struct ColumnsElem {
 /// understanding of the fundamental
 /// laws of nature.
 /// ```
+///
+/// # Conditional breaks { #conditional }
+/// The column break can be made conditional by passing a boolean. Combined
+/// with [context], this lets you break depending on the layout, e.g. only
+/// when some content turned out taller than expected. The condition is
+/// evaluated together with the surrounding context expression, which happens
+/// during layout and is repeated whenever the context changes.
+///
+/// ```example
+/// #set page(columns: 2, height: 80pt)
+/// #let intro = lorem(10)
+/// #intro
+/// #context colbreak(measure(intro).height > 20pt)
+/// Continues in the second column.
+/// ```
 #[elem(title = "Column Break")]
 pub struct ColbreakElem {
+    /// Whether to break at all. If `{false}`, the column break does nothing.
+    #[positional]
+    #[default(true)]
+    pub when: bool,
+
     /// If `{true}`, the column break is skipped if the current column is
     /// already empty.
     #[default(false)]
     pub weak: bool,
+
+    /// The column to continue in, starting from one.
+    ///
+    /// The columns between the current one and the given one stay empty. If
+    /// the given column is the current one or comes before it, the content
+    /// continues in that column of the next region. Breaking to a column
+    /// beyond the [filled columns]($columns.fill-columns) or to one that the
+    /// next region doesn't have due to a [schedule]($columns.count)
+    /// continues in the last filled one. It is an error to break to a column
+    /// that doesn't exist, even if the break is weak and would otherwise have
+    /// no effect.
+    ///
+    /// ```example
+    /// #columns(3)[
+    ///   Name
+    ///   #colbreak(to: 3)
+    ///   Signature
+    /// ]
+    /// ```
+    pub to: Option<NonZeroUsize>,
+
+    /// A function that styles the content after the break.
+    ///
+    /// The function receives the content from the break up to the next
+    /// column break or the end of the columns, whichever comes first, and
+    /// returns the styled content. Use it with set rules to, for instance,
+    /// start the next column with a different text size.
+    ///
+    /// This only applies to column breaks directly in the body of
+    /// [columns]($columns), not in nested content or for
+    /// [page-level columns]($page.columns).
+    ///
+    /// ```example
+    /// #columns(2)[
+    ///   #lorem(10)
+    ///   #colbreak(style: it => {
+    ///     set text(fill: eastern)
+    ///     it
+    ///   })
+    ///   #lorem(10)
+    /// ]
+    /// ```
+    pub style: Option<Func>,
+}
+
+/// Influences where columns break.
+///
+/// A column penalty marks a point in the flow at which a column may break and
+/// assigns a cost to breaking there. When a column overflows, Typst compares
+/// the natural break point (where the content stopped fitting) with all
+/// penalties in the current column and breaks at the cheapest one. The cost of
+/// breaking at a penalty is its value plus the amount of space (in points)
+/// that would be left empty at the bottom of the column compared to the
+/// natural break. The natural break costs nothing, unless it coincides with a
+/// penalty, in which case it costs that penalty.
+///
+/// Hence, negative penalties encourage breaking and positive ones discourage
+/// it. A penalty of `{-10000}` or lower forces a break, just like
+/// [`colbreak`].
+///
+/// # Example
+/// ```example
+/// #set page(columns: 2, height: 80pt)
+/// #lorem(10)
+///
+/// // Rather break here than in the
+/// // middle of the next paragraph.
+/// #colpenalty(-100)
+///
+/// #lorem(15)
+/// ```
+#[elem(title = "Column Penalty")]
+pub struct ColpenaltyElem {
+    /// The cost of breaking the column at this point.
+    #[required]
+    pub penalty: i64,
+}
+
+impl ColpenaltyElem {
+    /// Penalties at or below this value force a column break.
+    pub const FORCE: i64 = -10000;
+}
+
+/// Content that spans all columns.
+///
+/// When a column span is a top-level child of the body of a
+/// [columns]($columns) element, the columns before it are closed, the span is
+/// laid out across the full width, and the columns resume below it. This is
+/// useful for headlines and figures in magazine-style layouts.
+///
+/// The columns before a span are [balanced]($columns.balance), so that the
+/// span directly follows the shortest possible row of columns. The parts of
+/// the body and the spans are stacked like blocks: Each of them can break
+/// across regions and they don't expand to fill a region of fixed height, so
+/// any remaining space stays empty below the last part. Only the last part
+/// follows the `balance` setting of the columns.
+///
+/// Outside of a columns element, a column span is simply laid out as a
+/// block. For columns that span a whole page, use a
+/// [floating placement]($place.scope) instead.
+///
+/// # Example
+/// ```example
+/// #set page(height: 150pt)
+/// #columns(2)[
+///   #lorem(20)
+///   #colspan(align(center)[*Breaking News*])
+///   #lorem(20)
+/// ]
+/// ```
+#[elem(title = "Column Span")]
+pub struct ColspanElem {
+    /// The content that spans all columns.
+    #[required]
+    pub body: Content,
 }
@@ -1,8 +1,8 @@
 use crate::diag::{SourceResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Args, AutoValue, Construct, Content, NativeElement, Packed, Smart, StyleChain, Value,
-    cast, elem,
+    Args, AutoValue, Cast, Construct, Content, NativeElement, Packed, Smart, StyleChain,
+    Value, cast, elem,
 };
 use crate::introspection::Locator;
 use crate::layout::{
@@ -247,6 +247,12 @@ pub struct BlockElem {
     ///   lorem(15),
     /// )
     /// ```
+    ///
+    /// An unbreakable block that doesn't even fit into a full page or column
+    /// normally overflows it. If the block has a [label] and sits in
+    /// [columns]($columns), it is split after all and a warning is emitted.
+    /// This way, labelled content that is referenced elsewhere is kept
+    /// together whenever possible, but never lost beyond the column's bounds.
     #[default(true)]
     pub breakable: bool,
 
@@ -346,11 +352,40 @@ pub struct BlockElem {
     #[default(false)]
     pub sticky: bool,
 
+    /// Whether to force a break before this block.
+    ///
+    /// With `{"column"}`, the block always starts a new column (or page,
+    /// outside of columns), even if the current one still has space. Unlike a
+    /// [colbreak] placed in front of the block, this is a property of the
+    /// block itself and can thus be set with a show-set rule.
+    ///
+    /// The break is [weak]($colbreak.weak): If the block already is at the
+    /// start of a column, no empty column is produced.
+    ///
+    /// ```example
+    /// #set page(height: 120pt, columns: 2)
+    /// #show heading: set block(break-before: "column")
+    ///
+    /// = Introduction
+    /// #lorem(10)
+    ///
+    /// = Background
+    /// #lorem(10)
+    /// ```
+    pub break_before: Option<BlockBreak>,
+
     /// The contents of the block.
     #[positional]
     pub body: Option<BlockBody>,
 }
 
+/// Which kind of break to force before a block.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum BlockBreak {
+    /// Start a new column.
+    Column,
+}
+
 impl BlockElem {
     /// Create a block with a custom single-region layouter.
     ///
@@ -39,7 +39,15 @@ use crate::layout::{Abs, Em};
 /// - `abs`: A length with just the absolute component of the current length
 ///   (that is, excluding the `em` component).
 /// - `em`: The amount of `em` units in this length, as a [float].
-#[ty(scope, cast)]
+#[ty(
+    scope,
+    cast,
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    unit = "pt",
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Length {
     /// The absolute part.
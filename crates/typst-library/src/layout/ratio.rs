@@ -37,7 +37,14 @@ use crate::foundations::{Repr, repr, ty};
 ///
 /// When ratios are displayed in the document, they are rounded to two
 /// significant digits for readability.
-#[ty(cast)]
+#[ty(
+    cast,
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    unit = "%",
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Ratio(Scalar);
 
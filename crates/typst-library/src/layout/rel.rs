@@ -66,7 +66,15 @@ use crate::layout::{Abs, Em, Length, Ratio};
 /// #(100% - 50pt).length \
 /// #(100% - 50pt).ratio
 /// ```
-#[ty(cast, name = "relative", title = "Relative Length")]
+#[ty(
+    cast,
+    name = "relative",
+    title = "Relative Length",
+    operators = ["+", "-", "*", "/", "<", "<=", ">", ">="],
+    clone_cost = "cheap",
+    category = "numeric",
+    default,
+)]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Rel<T: Numeric = Length> {
     /// The relative part.
@@ -33,7 +33,9 @@ use typst_syntax::{FileId, Source, Span};
 use typst_utils::{LazyHash, SmallBitSet};
 
 use crate::diag::FileResult;
-use crate::foundations::{Array, Binding, Bytes, Datetime, Dict, Module, Scope, Styles};
+use crate::foundations::{
+    Array, Binding, Bytes, Datetime, Dict, Module, Scope, StrFormatRegistry, Styles,
+};
 use crate::layout::{Alignment, Dir};
 use crate::routines::Routines;
 use crate::text::{Font, FontBook};
@@ -157,6 +159,9 @@ pub struct Library {
     pub std: Binding,
     /// In-development features that were enabled.
     pub features: Features,
+    /// String conversions for types without a native one, used by the `str`
+    /// constructor.
+    pub str_formatters: StrFormatRegistry,
 }
 
 /// Configurable builder for the standard library.
@@ -167,6 +172,7 @@ pub struct LibraryBuilder {
     routines: &'static Routines,
     inputs: Option<Dict>,
     features: Features,
+    str_formatters: StrFormatRegistry,
 }
 
 impl LibraryBuilder {
@@ -177,6 +183,7 @@ impl LibraryBuilder {
             routines,
             inputs: None,
             features: Features::default(),
+            str_formatters: StrFormatRegistry::new(),
         }
     }
 
@@ -194,6 +201,13 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure how the `str` constructor converts values of types without
+    /// a native string conversion.
+    pub fn with_str_formatters(mut self, formatters: StrFormatRegistry) -> Self {
+        self.str_formatters = formatters;
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
@@ -205,6 +219,7 @@ impl LibraryBuilder {
             styles: Styles::new(),
             std: Binding::detached(global),
             features: self.features,
+            str_formatters: self.str_formatters,
         }
     }
 }
@@ -287,16 +302,7 @@ fn global(
     features: &Features,
 ) -> Module {
     let mut global = Scope::deduplicating();
-
-    self::foundations::define(&mut global, inputs, features);
-    self::model::define(&mut global);
-    self::text::define(&mut global);
-    self::layout::define(&mut global);
-    self::visualize::define(&mut global);
-    self::introspection::define(&mut global);
-    self::loading::define(&mut global);
-    self::symbols::define(&mut global);
-
+    define_categories(&mut global, inputs, features);
     global.define("math", math);
     global.define("pdf", self::pdf::module());
     if features.is_enabled(Feature::Html) {
@@ -308,6 +314,29 @@ fn global(
     Module::new("global", global)
 }
 
+/// Defines the global definitions of all categories that don't have their
+/// own module.
+fn define_categories(global: &mut Scope, inputs: Dict, features: &Features) {
+    self::foundations::define(global, inputs, features);
+    self::model::define(global);
+    self::text::define(global);
+    self::layout::define(global);
+    self::visualize::define(global);
+    self::introspection::define(global);
+    self::loading::define(global);
+    self::symbols::define(global);
+}
+
+/// A scope with the standard library's definitions that don't depend on the
+/// routines, i.e. everything except the HTML module.
+pub(crate) fn standard_scope() -> Scope {
+    let mut scope = Scope::new();
+    define_categories(&mut scope, Dict::new(), &Features::default());
+    scope.define("math", math::module());
+    scope.define("pdf", self::pdf::module());
+    scope
+}
+
 /// Defines scoped values that are globally available, too.
 fn prelude(global: &mut Scope) {
     global.define("black", Color::BLACK);
@@ -9,11 +9,11 @@ use crate::World;
 use crate::diag::SourceResult;
 use crate::engine::{Engine, Route, Sink, Traced};
 use crate::foundations::{
-    Args, Closure, Content, Context, Func, Module, NativeRuleMap, Scope, StyleChain,
-    Styles, Value,
+    Args, Closure, Content, Context, Func, Module, NativeRuleMap, Packed, Scope,
+    StyleChain, Styles, Value,
 };
 use crate::introspection::{Introspector, Locator, SplitLocator};
-use crate::layout::{Frame, Region};
+use crate::layout::{ColumnsElem, Frame, Region, Regions};
 use crate::model::DocumentInfo;
 
 /// Defines the `Routines` struct.
@@ -93,6 +93,15 @@ routines! {
         region: Region,
     ) -> SourceResult<Frame>
 
+    /// Determines the break opportunities in the body of a columns element.
+    fn column_break_candidates(
+        elem: &Packed<ColumnsElem>,
+        engine: &mut Engine,
+        locator: Locator,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Vec<usize>>
+
     /// Constructs the `html` module.
     fn html_module() -> Module
 }
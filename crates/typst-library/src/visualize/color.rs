@@ -188,7 +188,7 @@ static TO_SRGB: LazyLock<qcms::Transform> = LazyLock::new(|| {
 ///   )
 /// }))
 /// ```
-#[ty(scope, cast)]
+#[ty(scope, cast, clone_cost = "cheap")]
 #[derive(Copy, Clone)]
 pub enum Color {
     /// A 32-bit luma color.
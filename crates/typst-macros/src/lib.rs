@@ -133,6 +133,17 @@ pub fn func(stream: BoundaryStream, item: BoundaryStream) -> BoundaryStream {
 ///   Defaults to the Rust name in kebab-case.
 /// - `title`: The type's title case name (e.g. `String`). Defaults to the
 ///   normal name in title case.
+/// - `keywords = [..]`: A list of alternate search terms for this type.
+/// - `operators = [..]`: The operators that values of this type support, as
+///   spelled in Typst code.
+/// - `unit`: The suffix with which values of this type are displayed by
+///   default, for types with units (e.g. `pt` for lengths).
+/// - `clone_cost`: How expensive it is to clone values of this type, one of
+///   `"cheap"`, `"shared"`, or `"deep"`. Defaults to `"shared"`.
+/// - `category`: The broad category of the type, one of `"numeric"`,
+///   `"collection"`, `"textual"`, or `"other"`. Defaults to `"other"`.
+/// - `default`: Indicates that the type's `Default` implementation provides
+///   the value returned by `type.default`.
 #[proc_macro_attribute]
 pub fn ty(stream: BoundaryStream, item: BoundaryStream) -> BoundaryStream {
     let item = syn::parse_macro_input!(item as syn::Item);
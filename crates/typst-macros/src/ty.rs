@@ -5,7 +5,7 @@ use syn::{Attribute, Ident, Result};
 
 use crate::util::{
     BareType, determine_name_and_title, documentation, foundations, kw, parse_flag,
-    parse_string, parse_string_array,
+    parse_key_value, parse_string, parse_string_array,
 };
 
 /// Expand the `#[ty]` macro.
@@ -49,6 +49,16 @@ struct Meta {
     name: Option<String>,
     title: Option<String>,
     keywords: Vec<String>,
+    operators: Vec<String>,
+    unit: Option<String>,
+    /// How expensive it is to clone values of this type. `Shared` if not
+    /// given.
+    clone_cost: Option<Ident>,
+    /// The broad category of the type. `Other` if not given.
+    category: Option<Ident>,
+    /// Whether the type's `Default` implementation provides its default
+    /// value.
+    default: bool,
 }
 
 impl Parse for Meta {
@@ -59,10 +69,44 @@ impl Parse for Meta {
             name: parse_string::<kw::name>(input)?,
             title: parse_string::<kw::title>(input)?,
             keywords: parse_string_array::<kw::keywords>(input)?,
+            operators: parse_string_array::<kw::operators>(input)?,
+            unit: parse_string::<kw::unit>(input)?,
+            clone_cost: parse_clone_cost(input)?,
+            category: parse_category(input)?,
+            default: parse_flag::<kw::default>(input)?,
         })
     }
 }
 
+/// Parse the `clone_cost = ".."` metadata into a variant of `CloneCost`.
+fn parse_clone_cost(input: ParseStream) -> Result<Option<Ident>> {
+    let Some(lit) = parse_key_value::<kw::clone_cost, syn::LitStr>(input)? else {
+        return Ok(None);
+    };
+    let variant = match lit.value().as_str() {
+        "cheap" => "Cheap",
+        "shared" => "Shared",
+        "deep" => "Deep",
+        _ => bail!(lit, "expected `cheap`, `shared`, or `deep`"),
+    };
+    Ok(Some(Ident::new(variant, lit.span())))
+}
+
+/// Parse the `category = ".."` metadata into a variant of `TypeCategory`.
+fn parse_category(input: ParseStream) -> Result<Option<Ident>> {
+    let Some(lit) = parse_key_value::<kw::category, syn::LitStr>(input)? else {
+        return Ok(None);
+    };
+    let variant = match lit.value().as_str() {
+        "numeric" => "Numeric",
+        "collection" => "Collection",
+        "textual" => "Textual",
+        "other" => "Other",
+        _ => bail!(lit, "expected `numeric`, `collection`, `textual`, or `other`"),
+    };
+    Ok(Some(Ident::new(variant, lit.span())))
+}
+
 /// Parse details about the type from its definition.
 fn parse(meta: Meta, ident: Ident, attrs: &[Attribute]) -> Result<Type> {
     let docs = documentation(attrs);
@@ -75,7 +119,29 @@ fn parse(meta: Meta, ident: Ident, attrs: &[Attribute]) -> Result<Type> {
 /// Produce the output of the macro.
 fn create(ty: &Type, item: Option<&syn::Item>) -> TokenStream {
     let Type { ident, name, long, title, docs, meta, .. } = ty;
-    let Meta { keywords, .. } = meta;
+    let Meta { keywords, operators, .. } = meta;
+    let unit = match &meta.unit {
+        Some(unit) => quote! { Some(#unit) },
+        None => quote! { None },
+    };
+    let clone_cost = match &meta.clone_cost {
+        Some(variant) => quote! { #foundations::CloneCost::#variant },
+        None => quote! { #foundations::CloneCost::Shared },
+    };
+    let category = match &meta.category {
+        Some(variant) => quote! { #foundations::TypeCategory::#variant },
+        None => quote! { #foundations::TypeCategory::Other },
+    };
+
+    let default = if meta.default {
+        quote! {
+            Some(|| #foundations::IntoValue::into_value(
+                <#ident as ::std::default::Default>::default()
+            ))
+        }
+    } else {
+        quote! { None }
+    };
 
     let constructor = if meta.scope {
         quote! { <#ident as #foundations::NativeScope>::constructor() }
@@ -102,6 +168,12 @@ fn create(ty: &Type, item: Option<&syn::Item>) -> TokenStream {
             title: #title,
             docs: #docs,
             keywords: &[#(#keywords),*],
+            operators: &[#(#operators),*],
+            unit: #unit,
+            clone_cost: #clone_cost,
+            category: #category,
+            default: #default,
+            castable: <#ident as #foundations::Reflect>::castable,
             constructor: ::std::sync::LazyLock::new(|| #constructor),
             scope: ::std::sync::LazyLock::new(|| #scope),
         }
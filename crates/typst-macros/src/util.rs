@@ -258,5 +258,10 @@ pub mod kw {
     syn::custom_keyword!(cast);
     syn::custom_keyword!(constructor);
     syn::custom_keyword!(keywords);
+    syn::custom_keyword!(operators);
+    syn::custom_keyword!(unit);
+    syn::custom_keyword!(clone_cost);
+    syn::custom_keyword!(category);
+    syn::custom_keyword!(default);
     syn::custom_keyword!(parent);
 }
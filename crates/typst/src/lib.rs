@@ -357,5 +357,6 @@ pub static ROUTINES: LazyLock<Routines> = LazyLock::new(|| Routines {
     eval_closure: typst_eval::eval_closure,
     realize: typst_realize::realize,
     layout_frame: typst_layout::layout_frame,
+    column_break_candidates: typst_layout::column_break_candidates,
     html_module: typst_html::module,
 });
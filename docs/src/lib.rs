@@ -4,10 +4,12 @@ mod contribs;
 mod html;
 mod link;
 mod model;
+mod types;
 
 pub use self::contribs::*;
 pub use self::html::*;
 pub use self::model::*;
+pub use self::types::*;
 
 use ecow::{EcoString, eco_format};
 use heck::ToTitleCase;
@@ -0,0 +1,119 @@
+//! Presentation helpers for types, e.g. for value dumps or reference pages.
+
+use rustc_hash::FxHashMap;
+use typst::foundations::{
+    Args, Array, Content, Dict, Func, Label, Module, NativeElement, Packed, Str, Symbol,
+    Type,
+};
+use typst::layout::{Fr, Sizing, TrackSizings};
+use typst::model::{
+    StrongElem, TableCell, TableChild, TableElem, TableHeader, TableItem,
+};
+use typst::text::TextElem;
+use typst::visualize::Color;
+
+/// Colors for displaying values of each type, e.g. in a syntax highlighted
+/// dump of values.
+///
+/// Types of the same kind (numbers, text, collections, and so on) share a
+/// color from a default palette. The colors of individual types can be
+/// overridden with [`set`](Self::set).
+#[derive(Debug, Default, Clone)]
+pub struct TypePalette(FxHashMap<Type, Color>);
+
+impl TypePalette {
+    /// Creates a palette with just the default colors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the color of a type. Returns the previous override, if any.
+    pub fn set(&mut self, ty: Type, color: Color) -> Option<Color> {
+        self.0.insert(ty, color)
+    }
+
+    /// The color for displaying values of the given type.
+    pub fn color(&self, ty: Type) -> Color {
+        if let Some(&color) = self.0.get(&ty) {
+            return color;
+        }
+
+        let text = [Type::of::<Str>(), Type::of::<Symbol>(), Type::of::<Label>()];
+        let collections = [Type::of::<Array>(), Type::of::<Dict>(), Type::of::<Args>()];
+        let code = [Type::of::<Func>(), Type::of::<Type>(), Type::of::<Module>()];
+        if ty.is_numeric() {
+            Color::ORANGE
+        } else if text.contains(&ty) {
+            Color::GREEN
+        } else if collections.contains(&ty) {
+            Color::PURPLE
+        } else if code.contains(&ty) {
+            Color::BLUE
+        } else if ty == Type::of::<Content>() {
+            Color::TEAL
+        } else {
+            Color::GRAY
+        }
+    }
+}
+
+/// Creates a table of all types in the standard library with their names,
+/// titles, and summaries.
+///
+/// The table has a header row and one row per type, in the order in which
+/// the types are defined.
+pub fn types_table() -> Content {
+    let cell = |body: Content| TableItem::Cell(Packed::new(TableCell::new(body)));
+
+    let header = ["Name", "Title", "Summary"]
+        .map(|label| cell(StrongElem::new(TextElem::packed(label)).pack()));
+    let mut children =
+        vec![TableChild::Header(Packed::new(TableHeader::new(header.into())))];
+    for ty in Type::all() {
+        for text in [ty.short_name().into(), ty.title().into(), ty.docs_summary()] {
+            children.push(TableChild::Item(cell(TextElem::packed(text))));
+        }
+    }
+
+    let columns = [Sizing::Auto, Sizing::Auto, Sizing::Fr(Fr::one())];
+    TableElem::new(children)
+        .with_columns(TrackSizings(columns.into_iter().collect()))
+        .pack()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette() {
+        let int = Type::of::<i64>();
+        let float = Type::of::<f64>();
+        let str = Type::of::<Str>();
+        let mut palette = TypePalette::new();
+        assert_eq!(palette.color(int), palette.color(float));
+        assert_ne!(palette.color(int), palette.color(str));
+
+        assert_eq!(palette.set(int, Color::RED), None);
+        assert_eq!(palette.color(int), Color::RED);
+        assert_eq!(palette.color(float), Color::ORANGE);
+    }
+
+    #[test]
+    fn test_types_table() {
+        let table = types_table();
+        let table = table.to_packed::<TableElem>().unwrap();
+        let texts: Vec<_> = table
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                TableChild::Item(TableItem::Cell(cell)) => Some(cell.body.plain_text()),
+                _ => None,
+            })
+            .collect();
+        let i = texts.iter().position(|text| text == "int").unwrap();
+        assert_eq!(texts[i + 1], "Integer");
+        assert!(texts[i + 2].starts_with("A whole number"));
+        assert_eq!(texts.len(), 3 * Type::all().len());
+    }
+}
@@ -1,3 +1,4 @@
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
@@ -26,6 +27,10 @@ pub struct CliArguments {
     /// Updates the reference output of non-passing tests.
     #[arg(short, long, group = "action")]
     pub update: bool,
+    /// Measures how long it takes to compile each of the tests, averaged over
+    /// the given number of runs, instead of checking their output.
+    #[arg(long, group = "action", value_name = "RUNS")]
+    pub bench: Option<NonZeroUsize>,
     /// The scaling factor to render the output image with.
     ///
     /// Does not affect the comparison or the reference image.
@@ -1,6 +1,8 @@
 use std::fmt::Write;
+use std::num::NonZeroUsize;
 use std::ops::Range;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use ecow::eco_vec;
 use tiny_skia as sk;
@@ -23,6 +25,24 @@ pub fn run(test: &Test) -> TestResult {
     Runner::new(test).run()
 }
 
+/// Compiles a test the given number of times and prints how long a single
+/// compilation takes on average.
+pub fn bench(test: &Test, runs: NonZeroUsize) {
+    let world = TestWorld::new(test.source.clone());
+    let mut total = Duration::ZERO;
+    for _ in 0..runs.get() {
+        // Start each run from scratch instead of from the memoized results
+        // of the previous one.
+        comemo::evict(0);
+        let start = Instant::now();
+        let _ = typst::compile::<PagedDocument>(&world);
+        total += start.elapsed();
+    }
+
+    let mean = total / runs.get() as u32;
+    println!("{}: {mean:.2?} per compilation ({runs} runs)", test.name);
+}
+
 /// Write a line to a log sink, defaulting to the test's error log.
 macro_rules! log {
     (into: $sink:expr, $($tts:tt)*) => {
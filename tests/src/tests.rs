@@ -101,6 +101,16 @@ fn test() {
         return;
     }
 
+    #[cfg(feature = "default")]
+    if let Some(runs) = ARGS.bench {
+        // Run the benchmarks one after another so that they don't compete for
+        // the CPU.
+        for test in tests.iter() {
+            run::bench(test, runs);
+        }
+        return;
+    }
+
     let parser_dirs = ARGS.parser_compare.clone().map(create_syntax_store);
     #[cfg(not(feature = "default"))]
     let parser_dirs = parser_dirs.or_else(|| Some(create_syntax_store(None)));
@@ -11,9 +11,11 @@ use rustc_hash::FxHashMap;
 use typst::diag::{At, FileError, FileResult, SourceResult, StrResult, bail};
 use typst::engine::Engine;
 use typst::foundations::{
-    Array, Bytes, Context, Datetime, IntoValue, NoneValue, Repr, Smart, Value, func,
+    Array, Bytes, Content, Context, Datetime, IntoValue, NoneValue, Repr, Smart, Type,
+    Value, func,
 };
-use typst::layout::{Abs, Margin, PageElem};
+use typst::introspection::Locator;
+use typst::layout::{Abs, Alignment, Axes, ColumnsElem, Margin, PageElem, Regions, Size};
 use typst::model::{Numbering, NumberingPattern};
 use typst::syntax::{FileId, Source, Span};
 use typst::text::{Font, FontBook, TextElem, TextSize};
@@ -206,6 +208,7 @@ fn library() -> Library {
     lib.global.scope_mut().define_func::<test_repr>();
     lib.global.scope_mut().define_func::<print>();
     lib.global.scope_mut().define_func::<lines>();
+    lib.global.scope_mut().define_func::<break_candidates>();
     lib.global
         .scope_mut()
         .define("conifer", Color::from_u8(0x9f, 0xEB, 0x52, 0xFF));
@@ -213,6 +216,10 @@ fn library() -> Library {
         .scope_mut()
         .define("forest", Color::from_u8(0x43, 0xA1, 0x27, 0xFF));
 
+    // Let `str` convert alignments to test its formatter registry.
+    lib.str_formatters
+        .register(Type::of::<Alignment>(), |value| Ok(value.repr().into()));
+
     // Hook up default styles.
     lib.styles.set(PageElem::width, Smart::Custom(Abs::pt(120.0).into()));
     lib.styles.set(PageElem::height, Smart::Auto);
@@ -269,3 +276,22 @@ fn lines(
         .join(Some('\n'.into_value()), None)
         .at(span)
 }
+
+/// Determines the column break opportunities in a columns element, laid out
+/// with a width of 100pt.
+#[func]
+fn break_candidates(
+    engine: &mut Engine,
+    context: Tracked<Context>,
+    span: Span,
+    columns: Content,
+) -> SourceResult<Array> {
+    let Some(elem) = columns.to_packed::<ColumnsElem>() else {
+        bail!(span, "expected columns");
+    };
+    let styles = context.styles().at(span)?;
+    let regions =
+        Regions::repeat(Size::new(Abs::pt(100.0), Abs::inf()), Axes::splat(false));
+    let candidates = elem.break_candidates(engine, Locator::root(), styles, regions)?;
+    Ok(candidates.into_iter().map(|i| (i as i64).into_value()).collect())
+}
@@ -420,6 +420,18 @@
 #test(("Hello", "World", "Hi", "There").dedup(key: x => x.len()), ("Hello", "Hi"))
 #test(("Hello", "World", "Hi", "There").dedup(key: x => x.at(0)), ("Hello", "World", "There"))
 
+--- array-dedup-by ---
+// Test the `dedup` method with the `by` argument.
+#test((1, 1.0, 2).dedup(), (1, 2))
+#test((1, 1.0, 2).dedup(by: type.loose-eq), (1, 2))
+#test((1, 1.0, 2).dedup(by: (a, b) => type(a) == type(b) and a == b), (1, 1.0, 2))
+#test((1, 2, 3, 4).dedup(by: (a, b) => calc.abs(a - b) <= 1), (1, 3))
+#test(("a", "B", "b").dedup(key: lower, by: (a, b) => a == b), ("a", "B"))
+
+--- array-dedup-by-bad-return ---
+// Error: 2-31 expected boolean from `by` function, got integer
+#(1, 2).dedup(by: (a, b) => 1)
+
 --- array-to-dict ---
 // Test the `to-dict` method.
 #test(().to-dict(), (:))
@@ -88,3 +88,23 @@ sequence(
   gradient.linear(blue, red, space: rgb, angle: 45deg),
   `gradient.linear(angle: 45deg, space: rgb, (rgb("#0074d9"), 0%), (rgb("#ff4136"), 100%))`,
 )
+
+--- inspect-nested-dict ---
+#let inspected = inspect((a: 1, b: (c: "hi", d: (2.5,))))
+#test(inspected.func(), raw)
+#test(inspected.text.split("\n"), (
+  "dictionary",
+  "(a: 1, b: (c: \"hi\", d: (2.5,)))",
+  "a: integer = 1",
+  "b: dictionary",
+  "  c: string = \"hi\"",
+  "  d: array",
+  "    0: float = 2.5",
+))
+
+--- repr-constructor-call ---
+// Values of types with a constructor are shown as a call to it.
+#test(repr(version(1, 2)), "version(1, 2)")
+#test(repr(duration(days: 2)), "duration(days: 2)")
+#test(repr(datetime(year: 2024, month: 1, day: 2)), "datetime(year: 2024, month: 1, day: 2)")
+#test(eval(repr(version(1, 2))), version(1, 2))
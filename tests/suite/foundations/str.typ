@@ -65,6 +65,15 @@
 // Error: 18-19 base is only supported for integers
 #str(1.23, base: 2)
 
+--- str-constructor-registered-formatter ---
+// The test world registers a formatter for alignments.
+#test(str(left), "left")
+#test(str(top + right), "right + top")
+
+--- str-constructor-registered-formatter-base ---
+// Error: 18-19 base is only supported for integers
+#str(left, base: 2)
+
 --- str-from-and-to-unicode ---
 // Test the unicode function.
 #test(str.from-unicode(97), "a")
@@ -339,6 +348,15 @@
 #test("abc".rev(), "cba")
 #test("ax̂e".rev(), "ex̂a")
 
+--- str-bench-method-dispatch ---
+// Many method calls on strings in a tight loop. Mainly used for benchmarking
+// with `--bench`.
+#let words = range(2000).map(i => "word" + str(i))
+#test(
+  words.map(w => w.trim("w").replace("o", "0").len()).sum(),
+  words.map(w => w.len()).sum() - words.len(),
+)
+
 --- string-unclosed ---
 // Error: 2-2:1 unclosed string
 #"hello\"
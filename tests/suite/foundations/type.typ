@@ -21,3 +21,182 @@
 #test(repr(auto), "auto")
 #test(repr(type(none)), "type(none)")
 #test(repr(type(auto)), "type(auto)")
+
+--- type-info ---
+#let info = type.info(int)
+#test(info.keys(), ("name", "title", "long-name", "keywords", "has-constructor", "docs-summary"))
+#test(info.name, "int")
+#test(info.title, "Integer")
+#test(info.long-name, "integer")
+#test(info.keywords, ())
+#test(info.has-constructor, true)
+#test(info.docs-summary, "A whole number.")
+#test(type.info(content).has-constructor, false)
+#test(type.info(tiling).keywords, ("pattern",))
+#test(int.info(), info)
+
+--- type-info-static-only ---
+// Functions of `type` that don't describe a type aren't methods of types.
+// Error: 6-9 type integer does not contain field `all`
+#int.all()
+
+--- type-loose-eq ---
+#test(type.loose-eq((1, 2.0), (1.0, 2)), true)
+#test(type.loose-eq((a: 1, b: (2,)), (b: (2.0,), a: 1.0)), true)
+#test(type.loose-eq((1,), (2,)), false)
+#test(type.loose-eq((1,), (1, 1)), false)
+#test(type.loose-eq((a: 1), (b: 1)), false)
+#test(type.loose-eq("1", 1), false)
+
+--- type-coerce-array ---
+#test(type.coerce-array(("1", "2"), int), (1, 2))
+#test(type.coerce-array((1, "2.5", 3), float), (1.0, 2.5, 3.0))
+#test(type.coerce-array((), str), ())
+
+--- type-coerce-array-bad-value ---
+// Error: 2-36 failed to convert value at index 1: invalid integer: a
+#type.coerce-array(("1", "a"), int)
+
+--- type-operators ---
+#test(int.operators.slice(0, 4), ("+", "-", "*", "/"))
+#test("+" in str.operators, true)
+#test("-" in str.operators, false)
+#test(type(true).operators.contains("and"), true)
+#test(label.operators, ())
+
+--- type-unit ---
+#test(length.unit, "pt")
+#test(type(45deg).unit, "deg")
+#test(int.unit, none)
+
+--- type-is-callable ---
+#test(type(x => x).is-callable, true)
+#test(type(1).is-callable, false)
+#test(type.is-callable, true)
+#test(type(int).is-callable, true)
+
+--- type-is-dict-key ---
+#test(type.is-dict-key(str), true)
+#test(type.is-dict-key(int), false)
+#test(type.is-dict-key(label), false)
+
+--- type-fields ---
+#test("split" in type.fields(str), true)
+#test("from-unicode" in type.fields(str), true)
+#test(type.fields(type(none)), ())
+#test(str.fields(), type.fields(str))
+
+--- type-compatible ---
+#test(type.compatible(float, 1), true)
+#test(type.compatible(float, 1.5), true)
+#test(type.compatible(int, 1.0), false)
+#test(type.compatible(relative, 1pt), true)
+#test(type.compatible(content, "hi"), true)
+#test(type.compatible(stroke, 1pt), true)
+#test(type.compatible(str, 1), false)
+
+--- type-all ---
+#let all = type.all()
+#test(all.map(entry => entry.type).contains(int), true)
+#test(all.find(entry => entry.type == str).title, "String")
+#test(all.find(entry => entry.type == str).name, "str")
+#let names = all.map(entry => str(entry.type))
+#test(names, names.sorted())
+#test(names.contains("integer"), true)
+
+--- type-docs-keywords ---
+#test(type.docs(int).len() > 0, true)
+#test(type.keywords(tiling), ("pattern",))
+#test(type.keywords(int), ())
+
+--- type-constructor ---
+#test(type.constructor(int)("42"), 42)
+#test((1, 2).map(type.constructor(str)), ("1", "2"))
+#test(type.constructor(float).with(2)(), 2.0)
+
+--- type-constructor-missing ---
+// Error: 2-27 type content does not have a constructor
+#type.constructor(content)
+
+--- type-category ---
+#for ty in (int, float, decimal, length, angle, ratio, relative, fraction) {
+  test(type.category(ty), "numeric")
+}
+#test(type.category(array), "collection")
+#test(type.category(dictionary), "collection")
+#test(type.category(str), "textual")
+#test(type.category(content), "textual")
+#test(type.category(tiling), "other")
+#test(type.category(color), "other")
+#test(type.category(type), "other")
+
+--- type-category-field ---
+// Error: 6-14 type integer does not contain field `category`
+#int.category
+
+--- type-from-name ---
+#test(type.from-name("length"), length)
+#test(type.from-name("str"), str)
+#test(type.from-name("string"), str)
+#test(type.from-name("none"), type(none))
+#test(type.from-name("auto"), type(auto))
+
+--- type-from-name-unknown ---
+// Error: 2-24 unknown type `nope`
+#type.from-name("nope")
+
+--- type-title-slug ---
+#test(type.title(str), "String")
+#test(type.title(int), "Integer")
+#test(type.slug(str), "str")
+#test(type.slug(type(auto)), "auto")
+#test(type.slug(type(none)), "none")
+#let slugs = type.all().map(entry => type.slug(entry.type))
+#test(slugs.dedup().len(), slugs.len())
+
+--- type-compare ---
+#test((int, str, float).sorted(), (float, int, str))
+#test(int < str, true)
+#test(length >= length, true)
+#test(type(auto) < bool, true)
+#test(int < type(none), true)
+
+--- type-none-auto-names ---
+#test(str(type(none)), "none")
+#test(str(type(auto)), "auto")
+#test(type.title(type(none)), "None")
+
+--- type-has-constructor ---
+#test(type.has-constructor(int), true)
+#test(type.has-constructor(content), false)
+#let make(ty, value) = if type.has-constructor(ty) { ty(value) } else { none }
+#test(make(str, 1), "1")
+#test(make(content, 1), none)
+
+--- type-has-field ---
+#test(type.has-field(int, "signum"), true)
+#test(type.has-field(str, "split"), true)
+#test(type.has-field(int, "nonexistent"), false)
+
+--- type-default ---
+#test(type.default(int), 0)
+#test(type.default(float), 0.0)
+#test(type.default(length), 0pt)
+#test(type.default(ratio), 0%)
+#test(type.default(bool), false)
+#test(type.default(array), ())
+#test(type.default(dictionary), (:))
+#test(type.default(str), "")
+#test(("a", "b").fold(type.default(str), (acc, s) => acc + s), "ab")
+
+--- type-default-none ---
+// Error: 2-23 type content does not have a default value
+#type.default(content)
+
+--- type-repr-round-trip ---
+#test(repr(str), "str")
+#for entry in type.all() {
+  test(eval(repr(entry.type)), entry.type)
+}
+#test(eval(repr(type(none))), type(none))
+#test(eval(repr(type(auto))), type(auto))
@@ -93,6 +93,16 @@ and the document will grow with it.
 Only an explicit #colbreak() `#colbreak()` can put content in the
 second column.
 
+--- columns-set-page-exact-fit ---
+// Each column holds exactly four blocks, so the content fills the last
+// column of the third page without spilling onto a fourth one.
+#set page(height: 60pt, margin: 10pt, columns: 2)
+#set block(spacing: 0pt)
+#context test(counter(page).final(), (3,))
+#for i in range(24) {
+  block(width: 100%, height: 10pt, fill: aqua)[#i]
+}
+
 --- columns-one ---
 // Test a page with a single column.
 #set page(height: auto, width: 7.05cm, columns: 1)
@@ -129,3 +139,1068 @@ Hallo
 A
 #colbreak(weak: true)
 B
+
+--- colbreak-conditional ---
+// The break only happens if its condition, computed from context, holds.
+#let body(item) = columns(2, gutter: 0pt, {
+  block(height: 10pt)
+  context colbreak(measure(item).height > 20pt)
+  block(height: 10pt)
+})
+
+#context {
+  test(measure(width: 100pt, body(block(height: 30pt))).height, 10pt)
+  assert(measure(width: 100pt, body(block(height: 10pt))).height > 20pt)
+}
+
+--- colbreak-conditional-false ---
+#test(colbreak(false).when, false)
+
+--- columns-masonry ---
+// Each item goes into the currently shortest column.
+#set par(spacing: 2pt)
+#let body(..heights) = columns(2, gutter: 0pt, masonry: true, {
+  for h in heights.pos() {
+    block(height: h)
+  }
+})
+
+#context {
+  // Without masonry, the first column would have to hold all items.
+  test(measure(width: 100pt, body(30pt, 10pt, 10pt)).height, 30pt)
+  test(measure(width: 100pt, body(10pt, 30pt, 10pt, 10pt)).height, 34pt)
+}
+
+--- colpenalty-shifts-break ---
+// A negative penalty pulls the break to an earlier point.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, height: 25pt, columns(2, gutter: 0pt, {
+  [#metadata(none)<a>]
+  block(height: 10pt)
+  colpenalty(-20)
+  [#metadata(none)<b>]
+  block(height: 10pt)
+  block(height: 10pt)
+}))))
+
+#context test(locate(<b>).position().x, locate(<a>).position().x + 50pt)
+
+--- colpenalty-too-expensive ---
+// Breaking early would waste more space than the penalty gains.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, height: 25pt, columns(2, gutter: 0pt, {
+  [#metadata(none)<a>]
+  block(height: 10pt)
+  colpenalty(-5)
+  [#metadata(none)<b>]
+  block(height: 10pt)
+  block(height: 10pt)
+}))))
+
+#context test(locate(<b>).position().x, locate(<a>).position().x)
+
+--- colpenalty-force ---
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt, {
+  [#metadata(none)<a>]
+  block(height: 10pt)
+  colpenalty(-10000)
+  [#metadata(none)<b>]
+  block(height: 10pt)
+}))))
+
+#context test(locate(<b>).position().x, locate(<a>).position().x + 50pt)
+
+--- columns-avoid-single-region ---
+// Without shrinking, the last block would end up on a second page.
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 6pt)
+#columns(1, avoid-single-region: true, hide({
+  block(height: 1pt)
+  block(height: 1pt)
+  block(height: 1pt)
+  block(height: 1pt)
+  [#metadata(none) <end>]
+}))
+#context test(locate(<end>).page(), 1)
+
+--- columns-edge-rules ---
+// The rules should be at the same positions in both page columns, even though
+// the second one isn't full.
+#set page(columns: 2, height: 60pt)
+#columns(1, edge-rules: 1pt + red)[
+  #lorem(16)
+]
+
+--- columns-gap-before-after ---
+// The explicit gap wins over the smaller block spacing, but weak vertical
+// spacing wins over the gap.
+#place(hide(block(width: 100pt, {
+  set block(spacing: 5pt)
+  block(height: 10pt)[#metadata(none) <top>]
+  columns(1, gap-before: 20pt, gap-after: 2pt)[#metadata(none) <a>]
+  v(8pt, weak: true)
+  block(height: 10pt)[#metadata(none) <b>]
+})))
+
+#context {
+  let top = locate(<top>).position().y
+  let a = locate(<a>).position().y
+  test(a - top, 30pt)
+  test(locate(<b>).position().y - a, 8pt)
+}
+
+--- columns-labelled-unbreakable ---
+#place(hide(block(width: 100pt, height: 30pt, columns(2, gutter: 0pt)[
+  #metadata(none) <a>
+  #block(height: 20pt)
+  #block(height: 20pt, breakable: false)[#metadata(none) <b>] <kept>
+])))
+
+#context test(locate(<b>).position().x, locate(<a>).position().x + 50pt)
+
+--- columns-labelled-unbreakable-too-tall ---
+#place(hide(block(width: 100pt, height: 30pt, columns(2, gutter: 0pt)[
+  // Warning: 4-62 unbreakable block is taller than a full region and was split
+  // Hint: 4-62 try reducing the block's height or making it breakable
+  #block(height: 40pt, breakable: false)[#metadata(none) <c>] <tall>
+])))
+
+--- columns-labelled-unbreakable-too-tall-single ---
+// Outside of columns, the block silently overflows instead of being split.
+#place(hide(block(width: 100pt, height: 30pt)[
+  #block(height: 40pt, breakable: false)[#metadata(none) <d>] <tall>
+]))
+
+--- columns-fr-spacing-fills-column ---
+// Fractional spacing in a column that doesn't expand should still stretch to
+// the column's height.
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt)[
+  #block[#metadata(none) <top>]
+  #v(1fr)
+  #block[#metadata(none) <bottom>]
+])))
+
+#context test(locate(<bottom>).position().y - locate(<top>).position().y, 20pt)
+
+--- columns-pin ---
+#place(hide(block(width: 100pt, height: 30pt, columns(2, gutter: 0pt)[
+  #metadata(none) <a>
+  #columns.pin(column: 2, clearance: 5pt, block(height: 10pt)[
+    #metadata(none) <pin>
+  ])
+  #block(height: 25pt)
+  #block(height: 10pt)[#metadata(none) <b>]
+])))
+
+#context {
+  let a = locate(<a>).position()
+  let pin = locate(<pin>).position()
+  let b = locate(<b>).position()
+  test(pin.x - a.x, 50pt)
+  test(pin.y, a.y)
+  test(b.x - a.x, 50pt)
+  test(b.y - a.y, 15pt)
+}
+
+--- columns-trim ---
+// The blank lines at the top of the second column are dropped, so the second
+// "A" ends up at the same height as the first one.
+#place(hide(block(width: 100pt, height: 30pt, columns(2, gutter: 0pt, trim: true)[
+  A#box()<a> \ \ \ \ A#box()<b>
+])))
+
+#context {
+  let a = locate(<a>).position()
+  let b = locate(<b>).position()
+  test(b.x - a.x, 50pt)
+  test(b.y, a.y)
+}
+
+--- columns-rule-extend ---
+// With `region`, the rule and thus the columns span the full region height.
+#context {
+  let body = [Hello]
+  let content = columns(2, rule: 1pt)[#body]
+  let region = columns(2, rule: 1pt, rule-extend: "region")[#body]
+  test(measure(region, height: 50pt).height, 50pt)
+  assert(measure(content, height: 50pt).height < 50pt)
+  test(measure(content).height, measure(region).height)
+}
+
+--- columns-auto-count ---
+// At 10pt, a column should fit 65 characters of about 5pt each, so exactly
+// three columns fit into 975pt.
+#set text(10pt)
+#place(hide(block(width: 975pt, columns(auto, gutter: 0pt)[
+  #metadata(none) <a>
+  #colbreak()
+  #metadata(none) <b>
+  #colbreak()
+  #metadata(none) <c>
+])))
+
+#context {
+  test(locate(<b>).position().x - locate(<a>).position().x, 325pt)
+  test(locate(<c>).position().x - locate(<a>).position().x, 650pt)
+  test(locate(<c>).page(), 1)
+}
+
+--- columns-min-width ---
+// Three columns of at least 30pt and two gutters of 5pt fit into 100pt, so
+// each column is 30pt wide.
+#place(hide(block(width: 100pt, columns(30pt, gutter: 5pt)[
+  #block(width: 100%) <d>
+  #colbreak()
+  #block(width: 100%) <e>
+  #colbreak()
+  #block(width: 100%) <f>
+])))
+
+#context {
+  let (d, e, f) = (<d>, <e>, <f>).map(label => locate(label).position())
+  test(e.x - d.x, 35pt)
+  test(f.x - d.x, 70pt)
+  test(f.y, d.y)
+}
+
+--- columns-typographic-auto ---
+// Narrow columns are ragged and hyphenated, wide ones are justified.
+#set text(10pt)
+#place(hide(block(width: 100pt, columns(2, typographic-auto: true)[
+  #context test(par.justify, false)
+  #context test(text.hyphenate, true)
+])))
+#place(hide(block(width: 1000pt, columns(2, typographic-auto: true)[
+  #context test(par.justify, true)
+  #context test(text.hyphenate, auto)
+])))
+
+--- columns-gutter-continuation ---
+// The outer columns provide two regions for the inner ones. Only the first
+// region has a gutter.
+#place(hide(block(width: 200pt, height: 50pt, columns(2, gutter: 0pt)[
+  #columns(2, gutter: 20pt, gutter-continuation: 0pt)[
+    #metadata(none) <a>
+    #colbreak()
+    #metadata(none) <b>
+    #colbreak()
+    #metadata(none) <c>
+    #colbreak()
+    #metadata(none) <d>
+  ]
+])))
+
+#context {
+  let x(label) = locate(label).position().x
+  test(x(<b>) - x(<a>), 60pt)
+  test(x(<c>) - x(<a>), 100pt)
+  test(x(<d>) - x(<c>), 50pt)
+}
+
+--- columns-debug ---
+#set page(height: 60pt)
+#columns(2, debug: true)[
+  #lorem(12)
+]
+
+--- columns-fade-bottom ---
+// Only the second region is faded.
+#set page(columns: 2, height: 60pt)
+#columns(1, fade-bottom: 15pt)[
+  #lorem(24)
+]
+
+--- columns-column-heights ---
+// Fractional spacing fills each column, so it reveals the column's height.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, height: 100pt, columns(
+  2,
+  column-heights: (100%, 60%),
+)[
+  #block[#metadata(none) <a-top>]
+  #v(1fr)
+  #block[#metadata(none) <a-bottom>]
+  #colbreak()
+  #block[#metadata(none) <b-top>]
+  #v(1fr)
+  #block[#metadata(none) <b-bottom>]
+])))
+
+#context {
+  let y(label) = locate(label).position().y
+  test(y(<a-bottom>) - y(<a-top>), 100pt)
+  test(y(<b-bottom>) - y(<b-top>), 60pt)
+}
+
+--- columns-block-break-before ---
+// The second block starts a new column even though the first one has space.
+// The break is weak, so the first block doesn't leave an empty column.
+#set block(break-before: "column")
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt)[
+  #block[A] <a>
+  #block[B] <b>
+])))
+
+#context {
+  let (a, b) = (locate(<a>).position(), locate(<b>).position())
+  test(b.x - a.x, 50pt)
+  test(b.y, a.y)
+}
+
+--- columns-wide-child-overflow ---
+#place(hide(block(width: 100pt, columns(2)[
+  // Warning: 4-37 block is wider than all columns combined
+  // Hint: 4-37 set `columns.wide-child` to `"shrink"` or `"span"` to make it fit
+  #block(width: 150pt, height: 10pt)
+])))
+
+--- columns-wide-child-shrink ---
+// The block is scaled down to the width of a column.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt, wide-child: "shrink")[
+  #block(width: 200pt, height: 20pt) <wide>
+  #block[#metadata(none) <after>]
+])))
+
+#context test(locate(<after>).position().y - locate(<wide>).position().y, 5pt)
+
+--- columns-wide-child-span ---
+// The block floats to the top and spans both columns, so the content before
+// it ends up below it.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt, wide-child: "span")[
+  #block[#metadata(none) <before>]
+  #block(width: 150pt, height: 10pt)[#metadata(none) <inner>]
+])))
+
+#context {
+  let (before, inner) = (locate(<before>).position(), locate(<inner>).position())
+  test(inner.x, before.x)
+  test(inner.y < before.y, true)
+}
+
+--- columns-overfill-slack ---
+// The outer columns provide two regions for the inner ones. The content is
+// 5pt too tall for the first one, which is within the slack.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, height: 40pt, columns(2, gutter: 0pt)[
+  #columns(1, overfill-slack: 10pt)[
+    #block(height: 30pt)[#metadata(none) <a>]
+    #block(height: 15pt)[#metadata(none) <b>]
+  ]
+])))
+
+#context test(locate(<b>).position().x, locate(<a>).position().x)
+
+--- columns-overfill-slack-exceeded ---
+// The content is 15pt too tall, which exceeds the slack, so it spills.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, height: 40pt, columns(2, gutter: 0pt)[
+  #columns(1, overfill-slack: 10pt)[
+    #block(height: 30pt)[#metadata(none) <a>]
+    #block(height: 25pt, breakable: false)[#metadata(none) <b>]
+  ]
+])))
+
+#context test(locate(<b>).position().x - locate(<a>).position().x, 50pt)
+
+--- columns-break-candidates ---
+// No break can occur right after a sticky block.
+#context test(
+  break-candidates(columns(2)[
+    #block(sticky: true)[Heading]
+    #block[A]
+    #block[B]
+  ]),
+  (2,),
+)
+
+--- columns-break-candidates-widows-orphans ---
+// The first two and the last two lines are kept together.
+#context test(break-candidates(columns(2)[A \ B \ C \ D]), (2,))
+#context test(
+  break-candidates(columns(2)[
+    #set text(costs: (orphan: 0%, widow: 0%))
+    A \ B \ C \ D
+  ]),
+  (1, 2, 3),
+)
+
+--- columns-fill-columns ---
+// The content flows through the first two columns and then continues in the
+// next region instead of the third column.
+#set block(spacing: 0pt)
+#place(hide(block(width: 150pt, height: 40pt, columns(2, gutter: 0pt)[
+  #columns(3, gutter: 0pt, fill-columns: 2)[
+    #block(height: 40pt)[#metadata(none) <a>]
+    #block(height: 40pt)[#metadata(none) <b>]
+    #block(height: 40pt)[#metadata(none) <c>]
+  ]
+])))
+
+#context {
+  let (a, b, c) = (<a>, <b>, <c>).map(label => locate(label).position())
+  test(b.x - a.x, 25pt)
+  test(c.x - a.x, 75pt)
+  test(c.y, a.y)
+}
+
+--- columns-context-reflow ---
+// The height of the first block depends on the final value of a counter,
+// which is only known after the first layout iteration. The columns must be
+// laid out again with the new height, so the second block moves on to the
+// second column.
+#let c = counter("reflow")
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, height: 40pt, columns(2, gutter: 0pt)[
+  #context block(height: c.final().first() * 10pt)[#metadata(none) <a>]
+  #block(height: 20pt, breakable: false)[#metadata(none) <b>]
+  #c.update(3)
+])))
+
+#context test(locate(<b>).position().x - locate(<a>).position().x, 50pt)
+
+--- columns-bottom-rule ---
+// The rule spans both columns and the gutter and sits below the taller
+// column.
+#columns(2, bottom-rule: 1pt + red)[
+  #rect(width: 100%, height: 30pt, fill: aqua)
+  #colbreak()
+  #rect(width: 100%, height: 15pt, fill: aqua)
+]
+
+--- columns-space-distribution-between ---
+// The remaining 40pt are split evenly between the three blocks.
+#set block(spacing: 0pt)
+#place(hide(block(height: 100pt, columns(2, space-distribution: "between")[
+  #block(height: 20pt)[#metadata(none) <a>]
+  #block(height: 20pt)[#metadata(none) <b>]
+  #block(height: 20pt)[#metadata(none) <c>]
+])))
+
+#context {
+  let (a, b, c) = (<a>, <b>, <c>).map(label => locate(label).position())
+  test(b.y - a.y, 40pt)
+  test(c.y - b.y, 40pt)
+}
+
+--- columns-space-distribution-top ---
+// The content is pushed to the bottom of the column.
+#set block(spacing: 0pt)
+#place(hide(block(height: 100pt, columns(2, space-distribution: "top")[
+  #block(height: 20pt)[#metadata(none) <a>]
+  #block(height: 20pt)[#metadata(none) <b>]
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  // The page has a 10pt margin, so the column ends at 110pt.
+  test(a.y, 70pt)
+  test(b.y, 90pt)
+}
+
+--- columns-prefer-sentence-breaks ---
+// Each line is 10pt high and lines are 5pt apart, so two lines fit into a
+// column. The natural break after "Second part" falls mid-sentence, so the
+// column breaks after "First." instead.
+#set text(size: 10pt, top-edge: 10pt, bottom-edge: 0pt, costs: (widow: 0%, orphan: 0%))
+#set par(leading: 5pt)
+#let body(name) = [
+  First#metadata(name + "-a"). \
+  Second#metadata(name + "-b") part \
+  more
+]
+#block(width: 100pt, height: 30pt, columns(2, gutter: 0pt, body("natural")))
+#block(width: 100pt, height: 30pt, {
+  columns(2, gutter: 0pt, prefer-sentence-breaks: true, body("sentence"))
+})
+
+#context {
+  let pos(name) = locate(metadata.where(value: name)).position()
+  test(pos("natural-b").y - pos("natural-a").y, 15pt)
+  test(pos("sentence-b").y, pos("sentence-a").y)
+}
+
+--- columns-widths ---
+// The auto column takes the space left by the fixed one.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(gutter: 0pt, widths: (auto, 30pt))[
+  #block(width: 100%) <a>
+  #colbreak()
+  #block(width: 100%) <b>
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  test(b.x - a.x, 70pt)
+}
+
+--- columns-widths-rtl ---
+// In right-to-left text, the first column is on the right.
+#set text(dir: rtl)
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(gutter: 0pt, widths: (auto, 30pt))[
+  #block(width: 100%) <a>
+  #colbreak()
+  #block(width: 100%) <b>
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  test(a.x, 40pt)
+  test(b.x, 10pt)
+}
+
+--- columns-widths-fr ---
+// The fractional columns share the space left by the fixed one.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(gutter: 0pt, widths: (3fr, 1fr, 20pt))[
+  #block(width: 100%) <a>
+  #colbreak()
+  #block(width: 100%) <b>
+  #colbreak()
+  #block(width: 100%) <c>
+])))
+
+#context {
+  let (a, b, c) = (<a>, <b>, <c>).map(label => locate(label).position())
+  test(b.x - a.x, 60pt)
+  test(c.x - b.x, 20pt)
+}
+
+--- columns-widths-exceeding ---
+// Warning: 2-33 column widths and gutters exceed the available width
+// Hint: 2-33 use fractional widths to share the remaining space
+#columns(widths: (80pt, 40pt))[]
+
+--- columns-block-overflows-column ---
+#set page(height: 60pt)
+#columns(2, gutter: 0pt)[
+  // Warning: 4-48 block overflows column 1 by 20pt
+  // Hint: 4-48 try reducing the block's width or making the column wider
+  #block(width: 70pt, height: 10pt, fill: aqua)
+]
+
+--- columns-widths-count-mismatch ---
+// Error: 21-33 column count (3) does not match the number of widths (2)
+// Hint: 21-33 the count can be omitted when widths are given
+#columns(3, widths: (auto, auto))[]
+
+--- columns-rule-rtl ---
+// The rules sit in the middle of the gutters in right-to-left text, too, and
+// are as tall as the tallest column.
+#set text(dir: rtl)
+#columns(3, rule: 1pt + red)[
+  #rect(width: 100%, height: 30pt, fill: aqua)
+  #colbreak()
+  #rect(width: 100%, height: 15pt, fill: aqua)
+]
+
+--- columns-rule-regions ---
+// Each region gets its own rule segments.
+#set page(height: 80pt)
+#columns(2, rule: 1pt + red)[
+  #rect(width: 100%, height: 50pt, fill: aqua)
+  #rect(width: 100%, height: 50pt, fill: aqua)
+  #rect(width: 100%, height: 50pt, fill: aqua)
+]
+
+--- columns-fill ---
+// The fills span the columns' widths and the tallest column's height.
+#columns(3, fill: (aqua, none, yellow))[
+  #rect(width: 100%, height: 30pt, stroke: black)
+  #colbreak()
+  #rect(width: 100%, height: 15pt, stroke: black)
+]
+
+--- columns-fill-rtl ---
+// In right-to-left text, the first fill is in the rightmost column.
+#set text(dir: rtl)
+#columns(2, fill: (aqua, yellow))[
+  #rect(width: 100%, height: 20pt, stroke: black)
+]
+
+--- columns-min-height ---
+// Eight blocks fit onto a page, so the ninth one would be alone on the second
+// page. The minimum height pulls one block from each column over.
+#set page(height: 40pt, margin: 0pt)
+#set block(spacing: 0pt)
+#columns(2, gutter: 0pt, min-height: 20pt, {
+  for i in range(9) {
+    block(height: 10pt, width: 100%, breakable: false, fill: aqua, stroke: 0.5pt, metadata(i))
+  }
+})
+
+#context {
+  let at(i) = query(metadata).find(it => it.value == i).location()
+  test(at(5).page(), 1)
+  test(at(6).page(), 2)
+  test(at(8).position().y, 20pt)
+}
+
+--- columns-single-passthrough ---
+// A single column lays out exactly like its body, so both halves look the
+// same.
+#let body = [
+  #lorem(6) #metadata(none)<text>
+  #block(height: 10pt, width: 100%, fill: aqua)
+  #metadata(none)<after>
+  #lorem(3) #metadata(none)<end>
+]
+#grid(columns: (1fr, 1fr), column-gutter: 10pt, body, columns(1, body))
+
+#context for key in (<text>, <after>, <end>) {
+  let (plain, single) = query(key).map(it => it.location().position())
+  test(single.x - plain.x, 55pt)
+  test(single.y, plain.y)
+}
+
+--- columns-single-trim ---
+// A property that isn't known to have no effect on a single column keeps the
+// columns from being laid out like their body.
+#let body = [\ \ A#box()<pos>]
+#place(hide(block(width: 100pt, columns(1, body))))
+#place(hide(block(width: 100pt, columns(1, trim: true, body))))
+
+#context {
+  let (plain, trimmed) = query(<pos>).map(it => it.location().position())
+  test(trimmed.y < plain.y, true)
+}
+
+--- columns-markers ---
+// Each column in each region gets a marker at its top-left corner.
+#set page(width: 120pt, height: 60pt, margin: 10pt)
+#set block(spacing: 0pt)
+#columns(2, gutter: 10pt, markers: true, {
+  for _ in range(5) {
+    block(height: 15pt, width: 100%, breakable: false, fill: aqua, stroke: 0.5pt)
+  }
+})
+
+#context {
+  let markers = query(columns.marker)
+  test(markers.map(it => it.column), (1, 2, 1, 2))
+  test(markers.map(it => it.location().page()), (1, 1, 2, 2))
+  test(markers.map(it => it.location().position().x), (10pt, 65pt, 10pt, 65pt))
+  test(markers.map(it => it.width), (45pt,) * 4)
+}
+
+--- columns-markers-single ---
+// A single column still gets its marker.
+#place(hide(columns(1, markers: true, lorem(5))))
+#context test(query(columns.marker).map(it => it.column), (1,))
+
+--- columns-markers-page ---
+#set page(width: 120pt, height: 60pt, columns: 2)
+#set columns(markers: true)
+#lorem(5)
+
+#context test(query(columns.marker).len(), 2)
+
+--- columns-marker-construct ---
+// Error: 2-18 cannot be constructed manually
+#columns.marker()
+
+--- columns-gutter-nested ---
+// The inner gutter is 10% of the outer column's 50pt rather than of the
+// 100pt of the block.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt)[
+  #columns(2, gutter: 10%)[
+    #block(height: 10pt)[#metadata(none) <a>]
+    #colbreak()
+    #block(height: 10pt)[#metadata(none) <b>]
+  ]
+])))
+
+#context test(locate(<b>).position().x - locate(<a>).position().x, 27.5pt)
+
+--- columns-count-schedule ---
+// One column on the first page and two on the second one.
+#set page(width: 120pt, height: 60pt, margin: 10pt)
+#set block(spacing: 0pt)
+#columns((1, 2), gutter: 0pt, {
+  for i in range(6) {
+    block(height: 20pt, width: 100%, fill: aqua, stroke: 0.5pt, metadata(i))
+  }
+})
+
+#context {
+  let at(i) = query(metadata).find(it => it.value == i).location()
+  test(range(6).map(i => at(i).page()), (1, 1, 2, 2, 2, 2))
+  test(range(6).map(i => at(i).position().x), (10pt,) * 4 + (60pt,) * 2)
+}
+
+--- columns-count-schedule-colbreak-to ---
+// The break targets the third column of the next region, which only has one,
+// so the content continues in that one.
+#set block(spacing: 0pt)
+#place(hide(block(width: 90pt, height: 10pt, columns(2, gutter: 0pt)[
+  #columns((3, 1), gutter: 0pt)[
+    #block(height: 10pt) <a>
+    #colbreak(to: 3)
+    #block(height: 10pt)
+    #colbreak(to: 3)
+    #block(height: 10pt) <b>
+  ]
+])))
+
+#context test(locate(<b>).position().x - locate(<a>).position().x, 45pt)
+
+--- columns-count-schedule-gutters ---
+// The gutter only applies to the second page, which has two columns.
+#set page(width: 120pt, height: 30pt, margin: 10pt)
+#set block(spacing: 0pt)
+#columns((1, 2), gutter: 0pt, gutters: (20pt,), {
+  for i in range(3) {
+    block(height: 10pt, width: 100%, fill: aqua, stroke: 0.5pt, metadata(i))
+  }
+})
+
+#context {
+  let at(i) = query(metadata).find(it => it.value == i).location()
+  test(range(3).map(i => at(i).position().x), (10pt, 10pt, 70pt))
+}
+
+--- columns-count-schedule-gutters-mismatch ---
+// Error: 2-41 expected a region with 3 columns for 2 gutters
+// Hint: 2-41 there is one gutter between each pair of adjacent columns
+#columns((1, 2), gutters: (1em, 1em))[A]
+
+--- columns-count-schedule-empty ---
+// Error: 10-12 column count schedule must not be empty
+#columns((), [])
+
+--- columns-count-schedule-widths ---
+// Error: 2-42 individual column widths cannot be combined with a column count schedule
+#columns((1, 2), widths: (auto, auto))[A]
+
+--- columns-shrink-wrap ---
+// The second column starts right after the narrow first one and the gutter.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 10pt, shrink-wrap: true)[
+  #block(width: 20pt, height: 10pt)[#metadata(none) <a>]
+  #colbreak()
+  #block(width: 20pt, height: 10pt)[#metadata(none) <b>]
+])))
+
+#context test(locate(<b>).position().x - locate(<a>).position().x, 30pt)
+
+--- columns-shrink-wrap-rtl ---
+#set text(dir: rtl)
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 10pt, shrink-wrap: true)[
+  #block(width: 20pt, height: 10pt)[#metadata(none) <a>]
+  #colbreak()
+  #block(width: 20pt, height: 10pt)[#metadata(none) <b>]
+])))
+
+#context test(locate(<a>).position().x - locate(<b>).position().x, 30pt)
+
+--- columns-balance ---
+// Without balancing, all blocks end up in the first column of the unbounded
+// region. With balancing, each column receives two of them.
+#set block(spacing: 0pt)
+#let body = [
+  #block(height: 10pt) <a>
+  #block(height: 10pt)
+  #block(height: 10pt) <c>
+  #block(height: 10pt)
+]
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt, balance: true, body))))
+
+#context {
+  let (a, c) = (<a>, <c>).map(label => locate(label).position())
+  test(c.x - a.x, 50pt)
+  test(c.y, a.y)
+}
+
+--- columns-balance-default ---
+#set block(spacing: 0pt)
+#context {
+  let body = range(4).map(_ => block(height: 10pt)).join()
+  test(measure(block(width: 100pt, columns(2, body))).height, 40pt)
+  test(measure(block(width: 100pt, columns(2, balance: true, body))).height, 20pt)
+}
+
+--- columns-baseline-grid-page-origin ---
+// The first baselines of both columns snap to a page grid with lines 3pt
+// below every 12pt, regardless of their font size.
+#v(17pt)
+#context block(width: 100pt, columns(
+  2,
+  baseline-grid: 12pt,
+  grid-origin: 3pt - here().position().y,
+)[
+  First#metadata(none)<first> #colbreak()
+  #text(14pt)[Second#metadata(none)<second>]
+])
+
+#context for key in (<first>, <second>) {
+  let lines = (locate(key).position().y - 3pt) / 12pt
+  test(calc.abs(lines - calc.round(lines)) < 1e-6, true)
+}
+
+--- columns-baseline-grid-negative ---
+// Error: 2-36 baseline grid must be positive
+#columns(2, baseline-grid: -1pt)[A]
+
+--- columns-fill-order-row ---
+// The third block starts a new row below the taller one of the first row.
+#set par(spacing: 5pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt, fill-order: "row")[
+  #block(height: 10pt) <a>
+  #block(height: 20pt) <b>
+  #block(height: 10pt) <c>
+  #block(height: 10pt) <d>
+])))
+
+#context {
+  let (a, b, c, d) = (<a>, <b>, <c>, <d>).map(l => locate(l).position())
+  test(b.x - a.x, 50pt)
+  test(b.y, a.y)
+  test(c.x, a.x)
+  test(c.y - a.y, 25pt)
+  test(d.y, c.y)
+}
+
+--- columns-fill-order-row-rtl ---
+#set text(dir: rtl)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt, fill-order: "row")[
+  #block(width: 100%, height: 10pt) <a>
+  #block(width: 100%, height: 10pt) <b>
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(l => locate(l).position())
+  test(a.x - b.x, 50pt)
+  test(b.y, a.y)
+}
+
+--- colspan ---
+// The columns before the span are balanced and resume below it.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt)[
+  #block(height: 10pt) <a>
+  #block(height: 10pt) <b>
+  #colspan[#block(height: 5pt) <s>]
+  #block(height: 10pt) <c>
+  #block(height: 10pt) <d>
+])))
+
+#context {
+  let (a, b, s, c, d) = (<a>, <b>, <s>, <c>, <d>).map(l => locate(l).position())
+  test(b.x - a.x, 50pt)
+  test(b.y, a.y)
+  test(s.x, a.x)
+  test(s.y - a.y, 10pt)
+  test(c.y - a.y, 15pt)
+  test(d.x, c.x)
+}
+
+--- colspan-styled ---
+// Spans after a set rule are found as well.
+#set block(spacing: 0pt)
+#place(hide(block(width: 100pt, columns(2, gutter: 0pt)[
+  #set text(red)
+  #block(height: 10pt) <a>
+  #block(height: 10pt) <b>
+  #colspan[#block(height: 5pt) <s>]
+])))
+
+#context {
+  let (a, b, s) = (<a>, <b>, <s>).map(l => locate(l).position())
+  test(b.y, a.y)
+  test(s.y - a.y, 10pt)
+}
+
+--- colspan-outside-columns ---
+#colspan[Spans nothing]
+
+--- columns-gutters ---
+#set block(spacing: 0pt)
+#place(hide(block(width: 95pt, columns(3, gutters: (5pt, 15pt))[
+  #block(height: 10pt) <a>
+  #colbreak()
+  #block(height: 10pt) <b>
+  #colbreak()
+  #block(height: 10pt) <c>
+])))
+
+#context {
+  let (a, b, c) = (<a>, <b>, <c>).map(label => locate(label).position())
+  test(b.x - a.x, 30pt)
+  test(c.x - b.x, 40pt)
+}
+
+--- columns-gutters-auto ---
+// Auto entries fall back to the gutter.
+#set block(spacing: 0pt)
+#place(hide(block(width: 95pt, columns(3, gutter: 5pt, gutters: (auto, 15pt))[
+  #block(height: 10pt) <a>
+  #colbreak()
+  #block(height: 10pt) <b>
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  test(b.x - a.x, 30pt)
+}
+
+--- columns-gutters-count-mismatch ---
+// Error: 2-33 expected 2 gutters for 3 columns, found 1
+// Hint: 2-33 there is one gutter between each pair of adjacent columns
+#columns(3, gutters: (5pt,))[Hi]
+
+--- columns-widow-penalty ---
+// Without a penalty, the last line ends up alone in the second column. With
+// the penalty, breaking a line earlier is cheaper.
+#set text(top-edge: 10pt, bottom-edge: 0pt, costs: (widow: 0%, orphan: 0%))
+#set par(leading: 0pt)
+#let body(name) = [
+  A#metadata(name + "-a") \ B \ C#metadata(name + "-c") \ D
+]
+#let test-columns(name, ..args) = place(hide(block(
+  width: 100pt,
+  height: 35pt,
+  columns(2, gutter: 0pt, ..args, body(name)),
+)))
+#test-columns("plain")
+#test-columns("widow", widow-penalty: 100)
+
+#context {
+  let y(value) = query(metadata.where(value: value)).first().location().position().y
+  test(y("plain-c") - y("plain-a"), 20pt)
+  test(y("widow-c"), y("widow-a"))
+}
+
+--- columns-per-item ---
+// With three items per column, two items fit into a single column while
+// twenty items would need seven and are thus limited to four columns. The
+// columns are balanced so that each of them receives some of the items.
+#set block(spacing: 0pt)
+#let gallery(n) = place(hide(block(width: 100pt, columns(
+  (min: 1, max: 4, per-item: 3),
+  gutter: 0pt,
+  balance: true,
+  range(n).map(i => block(height: 2pt, metadata(i))).join(),
+))))
+#gallery(2)
+#gallery(20)
+
+#context {
+  let xs = query(metadata).map(m => m.location().position().x)
+  test(xs.slice(0, 2).dedup().len(), 1)
+  test(xs.slice(2).dedup().len(), 4)
+}
+
+--- columns-per-item-invalid ---
+// Error: 10-26 minimum column count must not exceed the maximum
+#columns((min: 3, max: 2))[]
+
+--- columns-bottom-align ---
+// The first column is stretched to the height of the second one. The third
+// column has no gap to stretch, so its block stays at the top.
+#set block(spacing: 5pt)
+#place(hide(block(width: 90pt, columns(3, gutter: 0pt, bottom-align: true)[
+  #block(height: 10pt) <a>
+  #block(height: 10pt) <b>
+  #colbreak()
+  #block(height: 40pt) <c>
+  #colbreak()
+  #block(height: 10pt) <d>
+])))
+
+#context {
+  let (a, b, c, d) = (<a>, <b>, <c>, <d>).map(label => locate(label).position())
+  test(b.y - a.y, 30pt)
+  test(c.y, a.y)
+  test(d.y, a.y)
+}
+
+--- colbreak-weak-consecutive ---
+// The second weak break lands at the top of the second column and collapses,
+// even though an absolutely placed element precedes it.
+#set block(spacing: 0pt)
+#place(hide(block(width: 90pt, columns(3, gutter: 0pt)[
+  #block(height: 10pt) <a>
+  #colbreak(weak: true)
+  #place(right)[x]
+  #colbreak(weak: true)
+  #block(height: 10pt) <b>
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  test(b.x - a.x, 30pt)
+  test(b.y, a.y)
+}
+
+--- colbreak-style ---
+// The style applies from the break up to the next one.
+#columns(3)[
+  #context test(text.size, 10pt)
+  #colbreak(style: it => {
+    set text(size: 20pt)
+    it
+  })
+  #context test(text.size, 20pt)
+  #colbreak()
+  #context test(text.size, 10pt)
+]
+
+--- colbreak-to ---
+// The second column stays empty.
+#set block(spacing: 0pt)
+#place(hide(block(width: 90pt, columns(3, gutter: 0pt)[
+  #block(height: 10pt) <a>
+  #colbreak(to: 3)
+  #block(height: 10pt) <b>
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  test(b.x - a.x, 60pt)
+  test(b.y, a.y)
+}
+
+--- colbreak-to-earlier ---
+// Breaking to an earlier column continues in that column of the next region.
+#set block(spacing: 0pt)
+#place(hide(block(width: 90pt, columns(2, gutter: 0pt)[
+  #columns(3, gutter: 0pt)[
+    #block(height: 10pt) <a>
+    #colbreak()
+    #block(height: 10pt)
+    #colbreak(to: 2)
+    #block(height: 10pt) <b>
+  ]
+])))
+
+#context {
+  let (a, b) = (<a>, <b>).map(label => locate(label).position())
+  test(b.x - a.x, 45pt + 15pt)
+}
+
+--- colbreak-to-missing-column ---
+// Error: 14-29 cannot break to column 3
+// Hint: 14-29 there are only 2 columns
+#columns(2)[#colbreak(to: 3)]
+
+--- colbreak-to-missing-column-weak ---
+// A weak break is rejected even where it would have no effect.
+// Error: 14-41 cannot break to column 3
+// Hint: 14-41 there are only 2 columns
+#columns(2)[#colbreak(weak: true, to: 3)]
+
+--- columns-bench-glossary ---
+// A glossary with many small two-column entries. Mainly used for benchmarking
+// with `--bench`.
+#let entry(i) = columns(2)[*Term #i* #colbreak() #lorem(5)]
+#context test(
+  measure(width: 100pt, range(500).map(entry).join()).height > 0pt,
+  true,
+)